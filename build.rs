@@ -0,0 +1,158 @@
+/// Generates code for the test macros of the solutions in `src/bin`:
+///
+/// - `OUT_DIR/golden/<day>.rs` holds a `golden_ignore!` macro that adds `#[ignore]` to the parts
+///   whose puzzle input or recorded answer is missing, so `golden_tests!` can skip them.
+/// - `OUT_DIR/cases/<day>.rs` holds a test for every case in `data/examples/<day>.cases`, which
+///   `example_cases!` includes.
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs};
+
+use tinyjson::JsonValue;
+
+#[allow(dead_code)]
+#[path = "src/template/cases/syntax.rs"]
+mod cases;
+// its tests are compiled, but not run, when the `test_lib` feature is enabled.
+#[allow(dead_code, unused_imports)]
+#[path = "src/template/config.rs"]
+mod config;
+
+/// Stands in for the library module `config.rs` takes the day type from.
+mod template {
+    pub type Day = u8;
+}
+
+fn main() {
+    let config = fs::read_to_string("aoc.toml").map_or_else(
+        |_| config::Config::default(),
+        |s| {
+            // the runner falls back to the defaults as well.
+            config::Config::from_str(&s).unwrap_or_else(|e| {
+                println!("cargo:warning=Failed to parse aoc.toml: {e}");
+                config::Config::default()
+            })
+        },
+    );
+    let data_dir = config.data_dir;
+    let answers_path = config.answers_path;
+    let answers = read_answers(&answers_path);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::create_dir_all(out_dir.join("golden")).unwrap();
+    fs::create_dir_all(out_dir.join("cases")).unwrap();
+
+    for entry in fs::read_dir("src/bin").unwrap() {
+        let path = entry.unwrap().path();
+        let Some(day) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let has_input = fs::metadata(data_dir.join("inputs").join(format!("{day}.txt")))
+            .is_ok_and(|metadata| metadata.len() > 0);
+        let mut arms = String::new();
+        for part in [1, 2] {
+            let reason = if !has_input {
+                "no puzzle input"
+            } else if !answers.contains(&(day.to_string(), part)) {
+                "no recorded answer, run `cargo time <day> --store` to record it"
+            } else {
+                continue;
+            };
+            arms += &format!(
+                "    ({part}, $($test:tt)*) => {{ #[ignore = \"{reason}\"] $($test)* }};\n"
+            );
+        }
+
+        let golden_ignore = format!(
+            "macro_rules! golden_ignore {{\n{arms}    ($part:literal, $($test:tt)*) => {{ $($test)* }};\n}}\n"
+        );
+        fs::write(
+            out_dir.join("golden").join(format!("{day}.rs")),
+            golden_ignore,
+        )
+        .unwrap();
+
+        let cases_path = data_dir.join("examples").join(format!("{day}.cases"));
+        fs::write(
+            out_dir.join("cases").join(format!("{day}.rs")),
+            case_tests(&cases_path),
+        )
+        .unwrap();
+    }
+
+    println!("cargo:rerun-if-changed=src/bin");
+    // only watch what the generated code depends on, `data` also holds files written by the
+    // runner, e.g. timings, which would otherwise trigger a rebuild.
+    let watched = [
+        PathBuf::from("aoc.toml"),
+        data_dir.join("inputs"),
+        data_dir.join("examples"),
+        // until the first answer is recorded, watch the folder it will be created in.
+        if answers_path.exists() {
+            answers_path
+        } else {
+            answers_path
+                .parent()
+                .map_or(data_dir.clone(), Path::to_path_buf)
+        },
+    ];
+    for path in watched {
+        // cargo re-runs build scripts on every build when a watched path does not exist.
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+}
+
+/// A test per case in a cases file. Errors in the file become compile errors, which only surface
+/// when a solution uses `example_cases!`.
+fn case_tests(path: &Path) -> String {
+    let Ok(file) = fs::read_to_string(path) else {
+        let message = format!("could not read {}.", path.display());
+        return format!("compile_error!({message:?});\n");
+    };
+    match cases::parse(&file) {
+        Ok(cases) => cases
+            .iter()
+            .map(|case| {
+                format!(
+                    "#[test]\nfn {0}() {{\n    check_case({0:?});\n}}\n",
+                    case.name
+                )
+            })
+            .collect(),
+        Err(e) => {
+            let message = format!("{}: line {}: {}", path.display(), e.line, e.message);
+            format!("compile_error!({message:?});\n")
+        }
+    }
+}
+
+/// The days and parts that have a recorded answer.
+fn read_answers(path: &Path) -> HashSet<(String, u8)> {
+    let json = fs::read_to_string(path)
+        .ok()
+        .and_then(|json| JsonValue::from_str(&json).ok());
+    let days = json
+        .as_ref()
+        .and_then(|json| json.get::<HashMap<String, JsonValue>>()?.get("data"))
+        .and_then(|data| data.get::<Vec<JsonValue>>());
+
+    let mut answers = HashSet::new();
+    for day in days.into_iter().flatten() {
+        let Some(day) = day.get::<HashMap<String, JsonValue>>() else {
+            continue;
+        };
+        let Some(name) = day.get("day").and_then(|name| name.get::<String>()) else {
+            continue;
+        };
+        for (part, key) in [(1, "part_1"), (2, "part_2")] {
+            if day.get(key).is_some_and(|answer| answer.is_string()) {
+                answers.insert((name.clone(), part));
+            }
+        }
+    }
+    answers
+}
@@ -2,20 +2,25 @@ use std::{collections::HashMap, vec};
 use topological_sort::TopologicalSort;
 advent_of_code::solution!(5);
 
-pub fn parse_num(input: &str) -> (u32, u32) {
-    let split = input.split("|").collect::<Vec<&str>>();
-    let left_num_str = split.first().unwrap();
-    let right_num_str = split.get(1).unwrap();
-    (
-        left_num_str.parse::<u32>().unwrap(),
-        right_num_str.parse::<u32>().unwrap(),
-    )
+pub fn parse_num(input: &str) -> Result<(u32, u32), String> {
+    let (left_num_str, right_num_str) = input
+        .split_once('|')
+        .ok_or_else(|| format!("expected an ordering rule like `47|53`, got `{input}`"))?;
+    let parse = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|e| format!("invalid page number `{s}` in rule `{input}`: {e}"))
+    };
+    Ok((parse(left_num_str)?, parse(right_num_str)?))
 }
 
-pub fn parse_vec(input: &str) -> Vec<u32> {
+pub fn parse_vec(input: &str) -> Result<Vec<u32>, String> {
     input
         .split(',')
-        .map(|x| x.trim().parse().unwrap())
+        .map(|x| {
+            x.trim()
+                .parse()
+                .map_err(|e| format!("invalid page number `{x}` in update `{input}`: {e}"))
+        })
         .collect()
 }
 
@@ -36,7 +41,7 @@ pub fn follow_rules(update: &[u32], rules: &Vec<(u32, u32)>) -> u32 {
     update[mid / 2]
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, String> {
     let mut page_ordering_rules = vec![];
     let mut start = false;
     let mut count: u32 = 0;
@@ -46,15 +51,15 @@ pub fn part_one(input: &str) -> Option<u32> {
             continue;
         }
         if start {
-            let pages_vec = parse_vec(line);
+            let pages_vec = parse_vec(line)?;
             let val = follow_rules(&pages_vec, &page_ordering_rules);
             count += val;
         } else {
-            let v = parse_num(line);
+            let v = parse_num(line)?;
             page_ordering_rules.push(v);
         }
     }
-    Some(count)
+    Ok(count)
 }
 
 pub fn get_unsafe_order_middle(update: &[u32], rules: &Vec<(u32, u32)>) -> u32 {
@@ -68,7 +73,7 @@ pub fn get_unsafe_order_middle(update: &[u32], rules: &Vec<(u32, u32)>) -> u32 {
     ts.collect::<Vec<u32>>()[middle]
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, String> {
     let mut page_ordering_rules = vec![];
     let mut start = false;
     let mut count: u32 = 0;
@@ -78,17 +83,17 @@ pub fn part_two(input: &str) -> Option<u32> {
             continue;
         }
         if start {
-            let pages_vec = parse_vec(line);
+            let pages_vec = parse_vec(line)?;
             let val = follow_rules(&pages_vec, &page_ordering_rules);
             if val == 0 {
                 count += get_unsafe_order_middle(&pages_vec, &page_ordering_rules);
             }
         } else {
-            let v = parse_num(line);
+            let v = parse_num(line)?;
             page_ordering_rules.push(v);
         }
     }
-    Some(count)
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -97,13 +102,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(123));
+    }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
     }
 }
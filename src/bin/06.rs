@@ -1,17 +1,17 @@
+use advent_of_code::bitset::{BitGrid, DirectionalBitGrid};
+use advent_of_code::cycle;
+use advent_of_code::geometry::{Direction, Point};
+use advent_of_code::template::visualize::{self, Color, Frame};
 use core::fmt;
 use rayon::prelude::*;
-use std::collections::HashSet;
 
-advent_of_code::solution!(6);
-
-const NORTH: (i32, i32) = (-1, 0);
-const SOUTH: (i32, i32) = (1, 0);
-const EAST: (i32, i32) = (0, 1);
-const WEST: (i32, i32) = (0, -1);
+advent_of_code::solution!(6, alternatives {
+    2 => [part_two_sequential, part_two_brent],
+});
 
 #[derive(Clone, Copy)]
 enum Objects {
-    Guard(i32, i32),
+    Guard(Direction),
     Obstacle,
     Path(bool),
 }
@@ -22,7 +22,7 @@ impl fmt::Debug for Objects {
             f,
             "{}",
             match self {
-                Objects::Guard(_, _) => '^',
+                Objects::Guard(_) => '^',
                 Objects::Obstacle => '#',
                 Objects::Path(b) =>
                     if *b {
@@ -35,31 +35,26 @@ impl fmt::Debug for Objects {
     }
 }
 
-fn pretty_print_matrix(matrix: &Vec<Vec<Objects>>) {
-    for row in matrix {
-        for obj in row {
-            print!("{:?} ", obj);
+fn matrix_frame(matrix: &[Vec<Objects>], guard: Option<Point>) -> Frame {
+    let mut frame = Frame::new(matrix.first().map_or(0, Vec::len), matrix.len());
+    for (row, line) in matrix.iter().enumerate() {
+        for (col, obj) in line.iter().enumerate() {
+            frame.set_char(col, row, format!("{obj:?}").chars().next().unwrap());
+            if let Objects::Path(true) = obj {
+                frame.set_color(col, row, Color::BLUE);
+            }
         }
-        println!(); // Move to the next line after each row
     }
-}
-
-fn add_tuple(u: (usize, usize), i: (i32, i32)) -> Option<(usize, usize)> {
-    let left = add(u.0, i.0)?;
-    let right = add(u.1, i.1)?;
-    Some((left, right))
-}
-
-fn add(u: usize, i: i32) -> Option<usize> {
-    if i.is_negative() {
-        u.checked_sub(i.wrapping_abs() as u32 as usize)
-    } else {
-        u.checked_add(i as usize)
+    if let Some(Point { x, y }) = guard {
+        frame.set_char(x, y, '@');
+        frame.set_color(x, y, Color::YELLOW);
     }
+    frame
 }
 
-fn parse_into_matrix(input: &str) -> ((usize, usize), Vec<Vec<Objects>>) {
-    let mut guard_position = (usize::MAX, usize::MAX);
+/// The guard's position and the map, or `None` if the map has no guard or an unknown character.
+fn parse_into_matrix(input: &str) -> Option<(Point, Vec<Vec<Objects>>)> {
+    let mut guard_position = None;
     let positions = input
         .lines()
         .enumerate()
@@ -67,30 +62,24 @@ fn parse_into_matrix(input: &str) -> ((usize, usize), Vec<Vec<Objects>>) {
             line.chars()
                 .enumerate()
                 .map(|(col, i): (usize, char)| match i {
-                    '.' => Objects::Path(false),
-                    'X' => Objects::Path(true),
-                    '#' => Objects::Obstacle,
-                    '^' | '<' | '>' | 'v' => {
-                        guard_position = (row, col);
-                        match i {
-                            '^' => Objects::Guard(NORTH.0, NORTH.1),
-                            '<' => Objects::Guard(WEST.0, WEST.1),
-                            '>' => Objects::Guard(EAST.0, EAST.1),
-                            'v' => Objects::Guard(SOUTH.0, SOUTH.1),
-                            _ => panic!(),
-                        }
+                    '.' => Some(Objects::Path(false)),
+                    'X' => Some(Objects::Path(true)),
+                    '#' => Some(Objects::Obstacle),
+                    _ => {
+                        let direction = Direction::from_arrow(i)?;
+                        guard_position = Some(Point::new(col, row));
+                        Some(Objects::Guard(direction))
                     }
-                    _ => panic!(),
                 })
-                .collect::<Vec<Objects>>()
+                .collect::<Option<Vec<Objects>>>()
         })
-        .collect::<Vec<Vec<Objects>>>();
-    (guard_position, positions)
+        .collect::<Option<Vec<Vec<Objects>>>>()?;
+    Some((guard_position?, positions))
 }
 
-fn get_next_pos(p: (usize, usize), v: &mut [Vec<Objects>]) -> Option<&mut Objects> {
-    if let Some(l) = v.get_mut(p.0) {
-        if let Some(g) = l.get_mut(p.1) {
+fn get_next_pos(p: Point, v: &mut [Vec<Objects>]) -> Option<&mut Objects> {
+    if let Some(l) = v.get_mut(p.y) {
+        if let Some(g) = l.get_mut(p.x) {
             Some(g)
         } else {
             None
@@ -100,81 +89,48 @@ fn get_next_pos(p: (usize, usize), v: &mut [Vec<Objects>]) -> Option<&mut Object
     }
 }
 
-fn check_if_loop(
-    mut matrix: Vec<Vec<Objects>>,
-    position: (usize, usize),
-    idx: (usize, usize),
-) -> (u32, bool) {
-    let mut count = 0u32;
-    matrix[idx.0][idx.1] = Objects::Obstacle;
-    let mut current_position = position;
-    let mut current_direction = NORTH;
-    let current_obj = &mut matrix[position.0][position.1];
-    let mut visited_states = HashSet::new();
-    if let Objects::Guard(d1, d2) = current_obj {
-        current_direction = (*d1, *d2);
-    }
-    *current_obj = Objects::Path(true);
-    count += 1;
-    while let Some(next_position) = add_tuple(current_position, current_direction) {
-        if let Some(next_pos_obj) = get_next_pos(next_position, &mut matrix) {
-            if !visited_states.insert((current_position, current_direction)) {
-                return (count, true);
-            }
-            match next_pos_obj {
-                Objects::Obstacle => match current_direction {
-                    NORTH => current_direction = EAST,
-                    EAST => current_direction = SOUTH,
-                    SOUTH => current_direction = WEST,
-                    WEST => current_direction = NORTH,
-                    _ => panic!("Direction didn't match anything"),
-                },
-                Objects::Path(b) => {
-                    if !*b {
-                        count += 1;
-                        *next_pos_obj = Objects::Path(true);
-                    }
-                    current_position = next_position;
-                }
-                _ => {
-                    pretty_print_matrix(&matrix);
-                    panic!("Not a obstacle or a path")
-                }
-            };
-            // dbg!(current_position, current_direction);
-        } else {
-            break;
+/// Whether the guard ends up walking in circles once an obstacle is placed at `obstacle`.
+fn check_if_loop(matrix: &[Vec<Objects>], position: Point, obstacle: Point) -> bool {
+    let direction = match matrix[position.y][position.x] {
+        Objects::Guard(direction) => direction,
+        _ => Direction::North,
+    };
+    // the walk ends when the guard leaves the map, so only a walk in circles has a cycle.
+    let walk = std::iter::successors(Some((position, direction)), |&(position, direction)| {
+        let next = position.step(direction)?;
+        match matrix.get(next.y)?.get(next.x)? {
+            Objects::Obstacle => Some((position, direction.turn_right())),
+            _ if next == obstacle => Some((position, direction.turn_right())),
+            _ => Some((next, direction)),
         }
-    }
-    (count, false)
+    });
+    cycle::brent(walk).is_some()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (position, mut matrix) = parse_into_matrix(input);
+    let (position, mut matrix) = parse_into_matrix(input)?;
     let mut count = 0;
     let mut current_position = position;
-    let mut current_direction = NORTH;
-    let current_obj = &mut matrix[position.0][position.1];
-    if let Objects::Guard(d1, d2) = current_obj {
-        current_direction = (*d1, *d2);
+    let mut current_direction = Direction::North;
+    let current_obj = &mut matrix[position.y][position.x];
+    if let Objects::Guard(direction) = current_obj {
+        current_direction = *direction;
     }
     *current_obj = Objects::Path(true);
     count += 1;
     let starting_direction = current_direction;
-    while let Some(next_position) = add_tuple(current_position, current_direction) {
+    while let Some(next_position) = current_position.step(current_direction) {
         if let Some(next_pos_obj) = get_next_pos(next_position, &mut matrix) {
             if current_position == position && starting_direction == current_direction && count != 1
             {
-                panic!("Loop");
+                // the guard walks in circles and never leaves the map.
+                return None;
             }
             match next_pos_obj {
-                Objects::Obstacle => match current_direction {
-                    NORTH => current_direction = EAST,
-                    EAST => current_direction = SOUTH,
-                    SOUTH => current_direction = WEST,
-                    WEST => current_direction = NORTH,
-                    _ => panic!("Direction didn't match anything"),
-                },
+                Objects::Obstacle => {
+                    current_direction = current_direction.turn_right();
+                    visualize::frame(|| matrix_frame(&matrix, Some(current_position)));
+                }
                 Objects::Path(b) => {
                     if !*b {
                         count += 1;
@@ -182,45 +138,148 @@ pub fn part_one(input: &str) -> Option<u32> {
                     }
                     current_position = next_position;
                 }
-                _ => {
-                    pretty_print_matrix(&matrix);
-                    panic!("Not a obstacle or a path")
-                }
+                // a second guard.
+                Objects::Guard(_) => return None,
             };
-            // dbg!(current_position, current_direction);
         } else {
             break;
         }
     }
+    visualize::frame(|| matrix_frame(&matrix, None));
     Some(count)
 }
 
+/// The obstacles of the map as a bit set, and the guard's starting direction.
+fn obstacle_grid(matrix: &[Vec<Objects>], position: Point) -> (BitGrid, Direction) {
+    let mut obstacles = BitGrid::new(matrix.first().map_or(0, Vec::len), matrix.len());
+    for (row, line) in matrix.iter().enumerate() {
+        for (col, obj) in line.iter().enumerate() {
+            if let Objects::Obstacle = obj {
+                obstacles.insert((row, col));
+            }
+        }
+    }
+    let direction = match matrix[position.y][position.x] {
+        Objects::Guard(direction) => direction,
+        _ => Direction::North,
+    };
+    (obstacles, direction)
+}
+
+/// Whether the guard starting at `position` ends up walking in circles once an obstacle is placed
+/// at `obstacle`. `visited` is cleared and reused, so no memory is allocated per walk.
+fn walks_in_circles(
+    obstacles: &BitGrid,
+    mut position: Point,
+    mut direction: Direction,
+    obstacle: Point,
+    visited: &mut DirectionalBitGrid,
+) -> bool {
+    visited.clear();
+    // the guard can only walk in circles by turning, so it is enough to remember the turns.
+    loop {
+        let Some(next) = position.step(direction).filter(|&p| obstacles.in_bounds(p)) else {
+            return false;
+        };
+        if next == obstacle || obstacles.contains(next) {
+            if !visited.insert(position, direction) {
+                return true;
+            }
+            direction = direction.turn_right();
+        } else {
+            position = next;
+        }
+    }
+}
+
+/// The cells the guard walks through without a new obstacle. An obstacle anywhere else does not
+/// change the walk, so these are the only candidates for the new obstacle.
+fn guard_path(obstacles: &BitGrid, mut position: Point, mut direction: Direction) -> BitGrid {
+    let mut path = BitGrid::new(obstacles.width(), obstacles.height());
+    path.insert(position);
+    while let Some(next) = position.step(direction).filter(|&p| obstacles.in_bounds(p)) {
+        if obstacles.contains(next) {
+            direction = direction.turn_right();
+        } else {
+            position = next;
+            path.insert(position);
+        }
+    }
+    path
+}
+
+/// The cells an obstacle can be placed on to make the guard walk in circles.
+fn candidates(obstacles: &BitGrid, position: Point, direction: Direction) -> Vec<Point> {
+    guard_path(obstacles, position, direction)
+        .iter()
+        .map(Point::from)
+        .filter(|&candidate| candidate != position)
+        .collect()
+}
+
 pub fn part_two(input: &str) -> Option<u32> {
-    let (position, matrix) = parse_into_matrix(input);
+    let (position, matrix) = parse_into_matrix(input)?;
+    let (obstacles, direction) = obstacle_grid(&matrix, position);
+    let (width, height) = (obstacles.width(), obstacles.height());
 
-    let count: u32 = matrix
+    let loops: Vec<Point> = candidates(&obstacles, position, direction)
+        .into_par_iter()
+        .map_init(
+            || DirectionalBitGrid::new(width, height),
+            |visited, obstacle| {
+                walks_in_circles(&obstacles, position, direction, obstacle, visited)
+                    .then_some(obstacle)
+            },
+        )
+        .flatten()
+        .collect();
+
+    visualize::frame(|| {
+        let mut frame = matrix_frame(&matrix, None);
+        for &Point { x, y } in &loops {
+            frame.set_char(x, y, 'O');
+            frame.set_color(x, y, Color::RED);
+        }
+        frame
+    });
+    Some(loops.len() as u32)
+}
+
+/// Same as `part_two`, without checking the candidate obstacles in parallel.
+pub fn part_two_sequential(input: &str) -> Option<u32> {
+    let (position, matrix) = parse_into_matrix(input)?;
+    let (obstacles, direction) = obstacle_grid(&matrix, position);
+    let mut visited = DirectionalBitGrid::new(obstacles.width(), obstacles.height());
+
+    let count = candidates(&obstacles, position, direction)
+        .into_iter()
+        .filter(|&obstacle| {
+            walks_in_circles(&obstacles, position, direction, obstacle, &mut visited)
+        })
+        .count();
+    Some(count as u32)
+}
+
+/// Same as `part_two`, trying every empty cell and detecting walks in circles with Brent's
+/// algorithm on the map itself instead of remembering visited states.
+pub fn part_two_brent(input: &str) -> Option<u32> {
+    let (position, matrix) = parse_into_matrix(input)?;
+
+    let empty_cells: Vec<Point> = matrix
         .iter()
         .enumerate()
-        .map(|(row_idx, row)| {
-            row.par_iter()
+        .flat_map(|(row, line)| {
+            line.iter()
                 .enumerate()
-                .filter_map(|(col_idx, col)| {
-                    if let Objects::Path(false) = col {
-                        let (_, loop_exist) =
-                            check_if_loop(matrix.clone(), position, (row_idx, col_idx));
-                        if loop_exist {
-                            Some(1)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
-                .sum::<u32>()
+                .filter(|(_, obj)| matches!(obj, Objects::Path(false)))
+                .map(move |(col, _)| Point::new(col, row))
         })
-        .sum();
-    Some(count)
+        .collect();
+    let count = empty_cells
+        .into_par_iter()
+        .filter(|&obstacle| check_if_loop(&matrix, position, obstacle))
+        .count();
+    Some(count as u32)
 }
 
 #[cfg(test)]
@@ -228,13 +287,33 @@ mod tests {
     use super::*;
     #[test]
     fn test_part_one() {
-        let result: Option<u32> = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result: Option<u32> =
+            part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_two_sequential() {
+        let result =
+            part_two_sequential(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two_brent() {
+        let result = part_two_brent(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Some(6));
+    }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        #[ignore = "slow in debug builds"]
+        2 => part_two,
+    }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::math::concat_digits;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 advent_of_code::solution!(7);
@@ -15,10 +16,8 @@ fn parse_input(input: &str) -> Vec<(u128, Vec<u128>)> {
     v
 }
 
-fn concat(a: u128, b: u128) -> u128 {
-    a * 10u128.pow(b.ilog10() + 1) + b
-}
-
+// not memoized: `(index, current_result)` almost never repeats within an equation, since the
+// operators rarely agree on a result, so a cache would only add hashing to every call.
 fn do_calculation(
     nums: &[u128],
     index: usize,
@@ -45,14 +44,11 @@ fn do_calculation(
     );
     let mut final_result = add_result || multiply_result;
     if need_concat_operator {
+        // a concatenation that overflows is far beyond any target.
         final_result = final_result
-            || do_calculation(
-                nums,
-                index + 1,
-                concat(current_result, nums[index + 1]),
-                need_concat_operator,
-                target,
-            );
+            || concat_digits(current_result, nums[index + 1]).is_some_and(|concatenated| {
+                do_calculation(nums, index + 1, concatenated, need_concat_operator, target)
+            });
     }
     final_result
 }
@@ -85,13 +81,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(11387));
     }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
+    }
 }
@@ -92,13 +92,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result: Option<u32> = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result: Option<u32> =
+            part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(34));
     }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
+    }
 }
@@ -1,5 +1,6 @@
+use advent_of_code::interval::{IntervalMap, IntervalSet};
 use core::fmt;
-use std::collections::HashSet;
+use std::ops::Range;
 
 advent_of_code::solution!(9);
 
@@ -62,25 +63,6 @@ fn parse_into_vec(input: &str, pack_as_one: bool) -> Vec<Objects> {
     v
 }
 
-fn convert_to_ones(input: &Vec<Objects>) -> Vec<Objects> {
-    let mut v = vec![];
-    for i in input {
-        match i {
-            Objects::File(id, num) => {
-                for _ in 0..*num {
-                    v.push(Objects::File(*id, 1));
-                }
-            }
-            Objects::FreeSpace(num) => {
-                for _ in 0..*num {
-                    v.push(Objects::FreeSpace(1));
-                }
-            }
-        }
-    }
-    v
-}
-
 pub fn part_one(input: &str) -> Option<u64> {
     let mut vec = parse_into_vec(input, true);
     let (mut i, mut j) = (0, vec.len() - 1);
@@ -105,54 +87,43 @@ pub fn part_one(input: &str) -> Option<u64> {
     )
 }
 
-fn try_swapping(vec: &mut Vec<Objects>, moved_objects: &mut HashSet<Objects>) -> bool {
-    let mut swap_count = 0;
-    for i in 0..vec.len() {
-        if let Objects::FreeSpace(numi) = vec[i] {
-            for j in (i..vec.len()).rev() {
-                if let Objects::File(_, numj) = vec[j] {
-                    if moved_objects.contains(&vec[j]) {
-                        continue;
-                    }
-                    match numi.cmp(&numj) {
-                        std::cmp::Ordering::Equal => {
-                            vec.swap(i, j);
-                            moved_objects.insert(vec[j]);
-                            swap_count += 1;
-                            break;
-                        }
-                        std::cmp::Ordering::Greater => {
-                            let diff = numi - numj;
-                            vec.swap(i, j);
-                            swap_count += 1;
-                            moved_objects.insert(vec[j]);
-                            vec[j] = Objects::FreeSpace(numj);
-                            vec.insert(i + 1, Objects::FreeSpace(diff));
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-            }
+/// The files on the disk, mapped to their ids, and the free space between them.
+fn parse_disk(input: &str) -> (IntervalMap<usize, u32>, IntervalSet<usize>) {
+    let mut files = IntervalMap::new();
+    let mut free = IntervalSet::new();
+    let mut position = 0;
+    for (idx, length) in input.chars().filter_map(|c| c.to_digit(10)).enumerate() {
+        let range = position..position + length as usize;
+        position = range.end;
+        if idx % 2 == 0 {
+            files.insert(range, idx as u32 / 2);
+        } else {
+            free.insert(range);
         }
     }
-    swap_count >= 1
+    (files, free)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut vec = parse_into_vec(input, false);
-    let mut moved_objects: HashSet<Objects> = HashSet::new();
-    loop {
-        if !try_swapping(&mut vec, &mut moved_objects) {
-            break;
+    let (mut files, mut free) = parse_disk(input);
+    // files are laid out by id, so moving them from the back moves them in decreasing id order.
+    let original: Vec<(Range<usize>, u32)> =
+        files.iter().rev().map(|(range, &id)| (range, id)).collect();
+    for (file, id) in original {
+        let length = file.len();
+        if let Some(gap) = free.first_fit(length).filter(|gap| gap.start < file.start) {
+            let target = gap.start..gap.start + length;
+            files.remove(file.clone());
+            files.insert(target.clone(), id);
+            free.remove(target);
+            free.insert(file);
         }
     }
     Some(
-        convert_to_ones(&vec)
+        files
             .iter()
-            .enumerate()
-            .map(|(idx, v)| v.checksum(idx))
-            .sum::<usize>(),
+            .map(|(range, &id)| range.sum::<usize>() * id as usize)
+            .sum(),
     )
 }
 
@@ -162,13 +133,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(2858));
     }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
+    }
 }
@@ -1,57 +1,49 @@
+use advent_of_code::math::{digit_count, split_digits};
+use advent_of_code::memo::Memo;
 use itertools::Itertools;
-use num_traits::Euclid;
-use std::collections::HashMap;
 
 advent_of_code::solution!(11);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let v = input
-        .replace("\n", "")
-        .split(" ")
-        .map(|value| value.parse::<u32>().unwrap())
-        .collect_vec();
-    let mut count = 0;
-    let mut count_map: HashMap<(u128, u32), u64> = HashMap::new();
-    for el in v {
-        count += blink(el as u128, 25, &mut count_map);
-    }
-    Some(count)
+fn parse_stones(input: &str) -> Vec<u128> {
+    input
+        .split_whitespace()
+        .map(|value| value.parse::<u128>().unwrap())
+        .collect_vec()
 }
 
-fn blink(num: u128, max_depth: u32, hash_map: &mut HashMap<(u128, u32), u64>) -> u64 {
-    let num_digits = num.checked_ilog10().unwrap_or(0) + 1;
-    let mut count = 0u64;
-    if let Some(v) = hash_map.get(&(num, max_depth)) {
-        return *v;
-    }
-    if max_depth == 0 {
-        hash_map.insert((num, max_depth), 1);
-        return 1;
-    }
-    if num == 0 {
-        count += blink(1, max_depth - 1, hash_map);
-    } else if num_digits % 2 == 0 {
-        let (left, right) = num.div_rem_euclid(&(10u128.pow(num_digits / 2)));
-        count += blink(left, max_depth - 1, hash_map) + blink(right, max_depth - 1, hash_map);
-    } else {
-        count += blink(num * 2024, max_depth - 1, hash_map);
-    }
-    hash_map.insert((num, max_depth), count);
-    count
+/// The number of stones `num` turns into after blinking `max_depth` times.
+fn blink(memo: &mut Memo<(u128, u32), u64>, num: u128, max_depth: u32) -> u64 {
+    memo.get_or_compute((num, max_depth), |memo| {
+        if max_depth == 0 {
+            return 1;
+        }
+        let num_digits = digit_count(num);
+        if num == 0 {
+            blink(memo, 1, max_depth - 1)
+        } else if num_digits.is_multiple_of(2) {
+            let (left, right) = split_digits(num, num_digits / 2);
+            blink(memo, left, max_depth - 1) + blink(memo, right, max_depth - 1)
+        } else {
+            let next = num.checked_mul(2024).expect("stone numbers fit into u128");
+            blink(memo, next, max_depth - 1)
+        }
+    })
+}
+
+fn count_stones(input: &str, blinks: u32) -> u64 {
+    let mut memo = Memo::new().named("blink");
+    parse_stones(input)
+        .into_iter()
+        .map(|stone| blink(&mut memo, stone, blinks))
+        .sum()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(count_stones(input, 25))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let v = input
-        .replace("\n", "")
-        .split(" ")
-        .map(|value| value.parse::<u32>().unwrap())
-        .collect_vec();
-    let mut count = 0;
-    let mut count_map: HashMap<(u128, u32), u64> = HashMap::new();
-    for el in v {
-        count += blink(el as u128, 75, &mut count_map);
-    }
-    Some(count)
+    Some(count_stones(input, 75))
 }
 
 #[cfg(test)]
@@ -60,13 +52,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(65601038650482));
     }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
+    }
 }
@@ -1,6 +1,6 @@
 advent_of_code::solution!(13);
 
-use itertools::Itertools;
+use advent_of_code::math::{self, SolveError};
 use regex::Regex;
 
 lazy_static::lazy_static! {
@@ -8,98 +8,105 @@ lazy_static::lazy_static! {
     static ref RE_PRICE: Regex = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
 }
 
-fn parse_inputs(inputs: &[&str]) -> Vec<i32> {
-    inputs
-        .iter()
-        .map(|&input| input.trim().parse::<i32>().expect("Invalid number format"))
+struct Machine {
+    a: (i128, i128),
+    b: (i128, i128),
+    prize: (i128, i128),
+}
+
+fn parse_machines(input: &str) -> Vec<Machine> {
+    let numbers = |re: &Regex, line: &str| {
+        let [x, y] = re
+            .captures(line)
+            .expect("Should be a valid machine line")
+            .extract()
+            .1
+            .map(|n| n.parse::<i128>().expect("Invalid number format"));
+        (x, y)
+    };
+    input
+        .trim()
+        .split("\n\n")
+        .map(|machine| {
+            let lines: Vec<&str> = machine.lines().collect();
+            Machine {
+                a: numbers(&RE_POINTS, lines[0]),
+                b: numbers(&RE_POINTS, lines[1]),
+                prize: numbers(&RE_PRICE, lines[2]),
+            }
+        })
         .collect()
 }
 
+/// The fewest tokens needed to win the prize, if it can be won. Pressing A costs 3 tokens and
+/// pressing B costs 1 token.
+fn tokens(machine: &Machine, offset: i128) -> Option<i128> {
+    let Machine { a, b, prize } = machine;
+    let prize = (prize.0 + offset, prize.1 + offset);
+
+    let (a_presses, b_presses) = match math::solve_2x2([[a.0, b.0], [a.1, b.1]], [prize.0, prize.1])
+    {
+        Ok([a_presses, b_presses]) => (a_presses.to_integer()?, b_presses.to_integer()?),
+        Err(SolveError::Singular) => parallel_presses(machine, prize)?,
+        Err(_) => return None,
+    };
+    (a_presses >= 0 && b_presses >= 0).then_some(3 * a_presses + b_presses)
+}
+
+/// The cheapest presses when both buttons move the claw along the same line, so there may be
+/// many ways to reach the prize.
+fn parallel_presses(machine: &Machine, prize: (i128, i128)) -> Option<(i128, i128)> {
+    let Machine { a, b, .. } = machine;
+    // use an axis on which the buttons move the claw.
+    let (ax, bx, px) = if (a.0, b.0) != (0, 0) {
+        (a.0, b.0, prize.0)
+    } else {
+        (a.1, b.1, prize.1)
+    };
+
+    // all solutions of `a * ax + b * bx = px` are `(a0 + k * s, b0 - k * t)`.
+    let (g, x, y) = math::extended_gcd(ax, bx)?;
+    if g == 0 || px % g != 0 {
+        return None;
+    }
+    let (a0, b0) = (x.checked_mul(px / g)?, y.checked_mul(px / g)?);
+    let (s, t) = (bx / g, ax / g);
+
+    // pressing A instead of B changes the cost by `3 * s - t` per step of `k`.
+    let k = if 3 * s > t {
+        // as few A presses as possible, keeping them non-negative.
+        (s > 0).then(|| -a0.div_euclid(s))?
+    } else {
+        // as few B presses as possible, keeping them non-negative.
+        (t > 0).then(|| b0.div_euclid(t))?
+    };
+    let presses = (
+        a0.checked_add(k.checked_mul(s)?)?,
+        b0.checked_sub(k.checked_mul(t)?)?,
+    );
+
+    let reaches = |a_step: i128, b_step: i128, target: i128| {
+        let a_part = a_step.checked_mul(presses.0);
+        let b_part = b_step.checked_mul(presses.1);
+        a_part.zip(b_part).and_then(|(p, q)| p.checked_add(q)) == Some(target)
+    };
+    (reaches(a.0, b.0, prize.0) && reaches(a.1, b.1, prize.1)).then_some(presses)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .split("\n\n")
-            .map(|test| {
-                let ins = test.split("\n").collect_vec();
-                let [a1_str, a2_str]: [&str; 2] = RE_POINTS
-                    .captures(ins[0])
-                    .expect("Should be a valid point line")
-                    .extract()
-                    .1;
-                let [b1_str, b2_str] = RE_POINTS
-                    .captures(ins[1])
-                    .expect("Should be a valid point line")
-                    .extract()
-                    .1;
-                let [c1_str, c2_str] = RE_PRICE
-                    .captures(ins[2])
-                    .expect("Should be a valid price line")
-                    .extract()
-                    .1;
-
-                if let [a1, a2, b1, b2, c1, c2] =
-                    parse_inputs(&[a1_str, a2_str, b1_str, b2_str, c1_str, c2_str])[..]
-                {
-                    let det = a1 * b2 - a2 * b1;
-                    let det_x = c1 * b2 - b1 * c2;
-                    let det_y = a1 * c2 - a2 * c1;
-                    let x = det_x / det;
-                    let y = det_y / det;
-
-                    if (x * a1 + b1 * y) == c1 && (x * a2 + b2 * y) == c2 {
-                        return x * 3 + y;
-                    }
-                }
-                0
-            })
-            .sum::<i32>() as u32,
-    )
+    let tokens: i128 = parse_machines(input)
+        .iter()
+        .filter_map(|machine| tokens(machine, 0))
+        .sum();
+    u32::try_from(tokens).ok()
 }
 
 pub fn part_two(input: &str) -> Option<u128> {
-    Some(
-        input
-            .split("\n\n")
-            .map(|test| {
-                let ins = test.split("\n").collect_vec();
-                let [a1_str, a2_str]: [&str; 2] = RE_POINTS
-                    .captures(ins[0])
-                    .expect("Should be a valid point line")
-                    .extract()
-                    .1;
-                let [b1_str, b2_str] = RE_POINTS
-                    .captures(ins[1])
-                    .expect("Should be a valid point line")
-                    .extract()
-                    .1;
-                let [c1_str, c2_str] = RE_PRICE
-                    .captures(ins[2])
-                    .expect("Should be a valid price line")
-                    .extract()
-                    .1;
-
-                if let [a1, a2, b1, b2, c1, c2] =
-                    parse_inputs(&[a1_str, a2_str, b1_str, b2_str, c1_str, c2_str])
-                        .iter()
-                        .map(|e| *e as i128)
-                        .collect_vec()[..]
-                {
-                    let c1 = 10_000_000_000_000 + c1;
-                    let c2 = 10_000_000_000_000 + c2;
-                    let det = a1 * b2 - a2 * b1;
-                    let det_x = c1 * b2 - b1 * c2;
-                    let det_y = a1 * c2 - a2 * c1;
-                    let x = det_x / det;
-                    let y = det_y / det;
-
-                    if (x * a1 + b1 * y) == c1 && (x * a2 + b2 * y) == c2 {
-                        return x * 3 + y;
-                    }
-                }
-                0
-            })
-            .sum::<i128>() as u128,
-    )
+    let tokens: i128 = parse_machines(input)
+        .iter()
+        .filter_map(|machine| tokens(machine, 10_000_000_000_000))
+        .sum();
+    u128::try_from(tokens).ok()
 }
 
 #[cfg(test)]
@@ -108,13 +115,31 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(875318608908));
     }
+
+    #[test]
+    fn test_parallel_buttons() {
+        let machine =
+            |prize: &str| format!("Button A: X+4, Y+4\nButton B: X+1, Y+1\nPrize: X={prize}\n");
+        // two presses of A are cheaper than eight presses of B.
+        assert_eq!(part_one(&machine("8, Y=8")), Some(6));
+        assert_eq!(part_one(&machine("9, Y=9")), Some(7));
+        assert_eq!(part_one(&machine("8, Y=9")), Some(0));
+
+        let cheap_b = "Button A: X+2, Y+4\nButton B: X+1, Y+2\nPrize: X=5, Y=10\n";
+        assert_eq!(part_one(cheap_b), Some(5));
+    }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
+    }
 }
@@ -1,119 +1,107 @@
+use advent_of_code::cycle;
+use advent_of_code::geometry::{Point, Vector};
+use advent_of_code::math;
+use advent_of_code::parse::{coordinates, lines, parse_all, ParseError};
+use advent_of_code::template::visualize::{self, Color, Frame};
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{char, digit1, line_ending},
-    combinator::{map_res, opt, recognize},
-    multi::separated_list1,
-    sequence::{pair, preceded, separated_pair},
-    IResult,
-};
-use std::str::FromStr;
+use nom::{character::complete::space1, sequence::separated_pair, IResult};
 advent_of_code::solution!(14);
 
-type Res<T, U> = IResult<T, U, nom::error::Error<T>>;
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Robot {
-    position: (i32, i32),
-    velocity: (i32, i32),
-}
-
-pub fn parse_numbers(input: &str) -> IResult<&str, i32> {
-    map_res(recognize(preceded(opt(tag("-")), digit1)), i32::from_str)(input)
+    position: Point,
+    velocity: Vector,
 }
 
 impl Robot {
-    fn parse(input: &str) -> Res<&str, Robot> {
-        let position = pair(
-            tag("p="),
-            separated_pair(parse_numbers, char(','), parse_numbers),
-        );
-        let velocity = pair(
-            tag("v="),
-            separated_pair(parse_numbers, char(','), parse_numbers),
-        );
-
-        let line = separated_pair(position, char(' '), velocity);
-        map_res(line, |((_, pos), (_, vel))| {
-            // Correctly return a Result
-            Ok::<Robot, nom::error::Error<&str>>(Robot {
-                position: (pos.1, pos.0),
-                velocity: (vel.1, vel.0),
-            })
-        })(input)
+    fn parse(input: &str) -> IResult<&str, Robot> {
+        let (rest, ((px, py), (vx, vy))) =
+            separated_pair(coordinates("p"), space1, coordinates("v"))(input)?;
+        let robot = Robot {
+            position: Point::new(px, py),
+            velocity: Vector::new(vx, vy),
+        };
+        Ok((rest, robot))
     }
 
-    fn move_unit_by(&mut self, times: u32, max: (i32, i32)) {
-        let x = (self.position.0 + (self.velocity.0 * times as i32)).rem_euclid(max.0);
-        let y = (self.position.1 + (self.velocity.1 * times as i32)).rem_euclid(max.1);
-        self.position = (x, y);
+    fn move_unit_by(&mut self, times: u32, (width, height): (usize, usize)) {
+        self.position = self
+            .position
+            .add_modulo(self.velocity * times as isize, width, height);
     }
 }
 
-pub fn parse_input(s: &str) -> Vec<Robot> {
-    let (_, lines) = separated_list1(line_ending, Robot::parse)(s).unwrap();
-    // assert!(remaining_input.is_empty());
-    lines
+pub fn parse_input(s: &str) -> Result<Vec<Robot>, ParseError> {
+    parse_all(s, lines(Robot::parse))
 }
 
-pub fn pretty_print_robots(robots: &[Robot], max: (i32, i32)) {
-    // Create a 2D vector initialized with zeroes for the grid
-    let mut grid = vec![vec![0; max.1 as usize]; max.0 as usize];
+/// The size of the area, taken from the robots furthest out.
+fn area(robots: &[Robot]) -> (usize, usize) {
+    let (min_x, max_x) = robots
+        .iter()
+        .map(|r| r.position.x)
+        .minmax()
+        .into_option()
+        .unwrap_or_default();
+    let (min_y, max_y) = robots
+        .iter()
+        .map(|r| r.position.y)
+        .minmax()
+        .into_option()
+        .unwrap_or_default();
+    (max_x - min_x + 1, max_y - min_y + 1)
+}
 
-    // Increment the count for each robot's position, adjusted for the grid bounds
+pub fn robots_frame(robots: &[Robot], (width, height): (usize, usize)) -> Frame {
+    let mut counts = vec![vec![0; width]; height];
     for robot in robots {
-        let x = robot.position.0 as usize % max.0 as usize;
-        let y = robot.position.1 as usize % max.1 as usize;
-        grid[x][y] += 1;
+        counts[robot.position.y][robot.position.x] += 1;
     }
 
-    // Print the grid
-    for row in grid {
-        for cell in row {
-            if cell == 0 {
-                print!(".");
+    let mut frame = Frame::new(width, height);
+    for (row, line) in counts.iter().enumerate() {
+        for (col, &count) in line.iter().enumerate() {
+            if count == 0 {
+                frame.set_char(col, row, '.');
             } else {
-                print!("{}", cell);
+                frame.set_char(col, row, char::from_digit(count.min(9), 10).unwrap());
+                frame.set_color(col, row, Color::GREEN);
             }
         }
-        println!(); // Move to the next line for the grid
     }
+    frame
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let mut robots = parse_input(input);
+pub fn part_one(input: &str) -> Result<i32, ParseError> {
+    let mut robots = parse_input(input)?;
 
-    let min_y = robots.iter().map(|r| r.position.0).min().unwrap_or(0);
-    let max_y = robots.iter().map(|r| r.position.0).max().unwrap_or(0);
-    let min_x = robots.iter().map(|r| r.position.1).min().unwrap_or(0);
-    let max_x = robots.iter().map(|r| r.position.1).max().unwrap_or(0);
-
-    let width = max_x - min_x + 1;
-    let height = max_y - min_y + 1;
-    let mid_x = height / 2;
-    let mid_y = width / 2;
+    let (width, height) = area(&robots);
+    let mid_x = width / 2;
+    let mid_y = height / 2;
     let (mut first, mut second, mut third, mut fourth) = (0, 0, 0, 0);
     for robot in &mut robots {
-        robot.move_unit_by(100, (height, width));
+        robot.move_unit_by(100, (width, height));
 
-        if robot.position.0 == mid_x || robot.position.1 == mid_y {
+        let Point { x, y } = robot.position;
+        if x == mid_x || y == mid_y {
             continue;
         }
 
-        if robot.position.0 < mid_x && robot.position.1 < mid_y {
+        if y < mid_y && x < mid_x {
             first += 1;
         }
-        if robot.position.0 < mid_x && robot.position.1 > mid_y {
+        if y < mid_y && x > mid_x {
             third += 1;
         }
-        if robot.position.0 > mid_x && robot.position.1 < mid_y {
+        if y > mid_y && x < mid_x {
             fourth += 1;
         }
-        if robot.position.0 > mid_x && robot.position.1 > mid_y {
+        if y > mid_y && x > mid_x {
             second += 1;
         }
     }
-    pretty_print_robots(&robots, (height, width));
-    Some(first * second * third * fourth)
+    visualize::frame(|| robots_frame(&robots, (width, height)));
+    Ok(first * second * third * fourth)
 }
 
 fn tree_test(robots: &[Robot]) -> bool {
@@ -124,53 +112,45 @@ fn tree_test(robots: &[Robot]) -> bool {
         .all_unique()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut robots = parse_input(input);
+/// The number of steps after which the robots' coordinates along one axis repeat.
+fn axis_period(
+    robots: &[Robot],
+    position: impl Fn(&Point) -> usize,
+    velocity: impl Fn(&Vector) -> isize,
+    size: usize,
+) -> usize {
+    let velocities: Vec<isize> = robots.iter().map(|r| velocity(&r.velocity)).collect();
+    let step = |coordinates: &Vec<usize>| {
+        coordinates
+            .iter()
+            .zip(&velocities)
+            .map(|(&c, &v)| (c as isize + v).rem_euclid(size as isize) as usize)
+            .collect()
+    };
+    let initial = robots.iter().map(|r| position(&r.position)).collect();
+    // every step is a permutation of the coordinates, so the sequence always repeats.
+    cycle::hashed(cycle::iterate(initial, step)).map_or(1, |cycle| cycle.length)
+}
 
-    let min_y = robots.iter().map(|r| r.position.0).min().unwrap_or(0);
-    let max_y = robots.iter().map(|r| r.position.0).max().unwrap_or(0);
-    let min_x = robots.iter().map(|r| r.position.1).min().unwrap_or(0);
-    let max_x = robots.iter().map(|r| r.position.1).max().unwrap_or(0);
+pub fn part_two(input: &str) -> Result<u32, String> {
+    let mut robots = parse_input(input).map_err(|e| e.to_string())?;
+    let (width, height) = area(&robots);
 
-    let width = max_x - min_x + 1;
-    let height = max_y - min_y + 1;
-    let mid_x = height / 2;
-    let mid_y = width / 2;
-    let (mut first, mut second, mut third, mut fourth) = (0, 0, 0, 0);
-    let mut i = 0;
-    loop {
+    // the robots move along x and y independently, and each axis repeats within `width` and
+    // `height` steps, so the arrangements repeat after the least common multiple of both periods.
+    let period_x = axis_period(&robots, |p| p.x, |v| v.x, width);
+    let period_y = axis_period(&robots, |p| p.y, |v| v.y, height);
+    let period = math::lcm(period_x, period_y).ok_or("the area is too large")?;
+    for i in 1..=period {
         for robot in &mut robots {
-            robot.move_unit_by(1, (height, width));
-
-            if robot.position.0 == mid_x || robot.position.1 == mid_y {
-                continue;
-            }
-
-            if robot.position.0 < mid_x && robot.position.1 < mid_y {
-                first += 1;
-            }
-            if robot.position.0 < mid_x && robot.position.1 > mid_y {
-                third += 1;
-            }
-            if robot.position.0 > mid_x && robot.position.1 < mid_y {
-                fourth += 1;
-            }
-            if robot.position.0 > mid_x && robot.position.1 > mid_y {
-                second += 1;
-            }
+            robot.move_unit_by(1, (width, height));
         }
-        i += 1;
         if tree_test(&robots) {
-            pretty_print_robots(&robots, (height, width));
-            println!(
-                "\nSafety factor: {}, i: {}",
-                first * second * third * fourth,
-                i
-            );
-            break;
+            visualize::frame(|| robots_frame(&robots, (width, height)));
+            return u32::try_from(i).map_err(|e| e.to_string());
         }
     }
-    Some(i)
+    Err("the robots never stop overlapping".into())
 }
 
 #[cfg(test)]
@@ -179,13 +159,18 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(1));
+    }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
     }
 }
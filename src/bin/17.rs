@@ -1,6 +1,7 @@
 use core::panic;
 use std::fmt::Debug;
 
+use advent_of_code::parse::{key_value, parse_all, unsigned};
 use itertools::Itertools;
 use num_traits::pow;
 
@@ -18,52 +19,55 @@ enum Instructions {
     Cdv(u8),
 }
 
-fn parse_digits_from_string(input: &str) -> u64 {
-    input
-        .chars()
-        .filter(|c| c.is_numeric())
-        .collect::<String>()
-        .parse::<u64>()
-        .expect("to be good integer")
+fn parse_register(line: &str) -> Result<u64, String> {
+    let (_, value) = parse_all(line, key_value(unsigned)).map_err(|e| e.to_string())?;
+    Ok(value)
 }
 
-fn parse_instructions(input: &str) -> (Vec<u64>, Vec<Instructions>) {
+fn parse_instructions(input: &str) -> Result<(Vec<u64>, Vec<Instructions>), String> {
     let input_numbers = input.replace("Program: ", "");
     let mut input_string = vec![];
     let out = input_numbers
         .split(",")
         .tuples()
         .map(|(opcode, operand)| {
-            let operand_num = operand.parse::<u8>().unwrap();
-            input_string.push(opcode.parse::<u64>().unwrap());
+            let operand_num = operand
+                .parse::<u8>()
+                .map_err(|e| format!("invalid operand `{operand}`: {e}"))?;
+            input_string.push(
+                opcode
+                    .parse::<u64>()
+                    .map_err(|e| format!("invalid opcode `{opcode}`: {e}"))?,
+            );
             input_string.push(operand_num as u64);
             match opcode {
-                "0" => Instructions::Adv(operand_num),
-                "1" => Instructions::Bxl(operand_num),
-                "2" => Instructions::Bst(operand_num),
-                "3" => Instructions::Jnz(operand_num as usize),
-                "4" => Instructions::Bxc(operand_num),
-                "5" => Instructions::Out(operand_num),
-                "6" => Instructions::Bdv(operand_num),
-                "7" => Instructions::Cdv(operand_num),
-                _ => panic!("Invalid instructions!!: {},{}", opcode, operand_num),
+                "0" => Ok(Instructions::Adv(operand_num)),
+                "1" => Ok(Instructions::Bxl(operand_num)),
+                "2" => Ok(Instructions::Bst(operand_num)),
+                "3" => Ok(Instructions::Jnz(operand_num as usize)),
+                "4" => Ok(Instructions::Bxc(operand_num)),
+                "5" => Ok(Instructions::Out(operand_num)),
+                "6" => Ok(Instructions::Bdv(operand_num)),
+                "7" => Ok(Instructions::Cdv(operand_num)),
+                _ => Err(format!("invalid instruction `{opcode},{operand_num}`")),
             }
         })
-        .collect_vec();
-    (input_string, out)
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok((input_string, out))
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str) -> Result<String, String> {
     let mut lines = input.lines();
-    let reg_a = parse_digits_from_string(lines.next().unwrap());
+    let reg_a = parse_register(lines.next().ok_or("missing register A")?)?;
 
-    let (_, instructions) = parse_instructions(lines.nth(3)?);
-    Some(run(&instructions, reg_a).iter().join(","))
+    let (_, instructions) = parse_instructions(lines.nth(3).ok_or("missing program")?)?;
+    Ok(run(&instructions, reg_a).iter().join(","))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Result<u64, String> {
     let mut lines = input.lines();
-    let (expected_output, instructions) = parse_instructions(lines.nth(4)?);
+    let (expected_output, instructions) =
+        parse_instructions(lines.nth(4).ok_or("missing program")?)?;
 
     let mut result = u64::MAX;
 
@@ -97,7 +101,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         // prepare next run with all generated numbers
         inputs = next;
     }
-    Some(result)
+    Ok(result)
 }
 
 fn run(ins: &[Instructions], reg_a_orig: u64) -> Vec<u64> {
@@ -158,13 +162,27 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("5,7,3,0".to_string()));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok("5,7,3,0".to_string()));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(117440));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
+        assert_eq!(result, Ok(117440));
+    }
+
+    #[test]
+    fn test_rejects_negative_register() {
+        let input = "Register A: -5\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,5,4,3,0\n";
+        assert_eq!(
+            part_one(input),
+            Err("line 1, column 13: expected a number, found `-5`.".to_string())
+        );
+    }
+
+    advent_of_code::golden_tests! {
+        1 => part_one,
+        2 => part_two,
     }
 }
@@ -0,0 +1,396 @@
+/// Sets of grid positions stored as one bit per cell, for tracking visited cells in hot loops.
+///
+/// Testing, setting and unsetting a bit takes constant time without hashing. The sets remember
+/// which words they touched, so `clear` only takes as long as the number of inserts since the
+/// last clear, and one set can be reused across many short simulations on a large grid.
+/// [`DirectionalBitGrid`] additionally stores the heading, for states like a guard's position and
+/// direction.
+use crate::geometry::Direction;
+use crate::grid::{Grid, Position};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of positions in a `width` by `height` grid.
+#[derive(Clone, Debug)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+    /// The indices of words that were empty before an insert since the last clear.
+    touched: Vec<usize>,
+}
+
+impl PartialEq for BitGrid {
+    fn eq(&self, other: &Self) -> bool {
+        (self.width, self.height) == (other.width, other.height) && self.words == other.words
+    }
+}
+
+impl Eq for BitGrid {}
+
+impl BitGrid {
+    /// Creates an empty set.
+    pub fn new(width: usize, height: usize) -> Self {
+        BitGrid {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(WORD_BITS)],
+            touched: vec![],
+        }
+    }
+
+    /// Creates the set of positions whose cell matches `predicate`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut predicate: impl FnMut(&T) -> bool) -> Self {
+        let mut set = BitGrid::new(grid.width(), grid.height());
+        for (position, cell) in grid.iter() {
+            if predicate(cell) {
+                set.insert(position);
+            }
+        }
+        set
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a position lies within the grid.
+    pub fn in_bounds(&self, position: impl Into<Position>) -> bool {
+        let (row, col) = position.into();
+        row < self.height && col < self.width
+    }
+
+    /// Whether a position is in the set. Positions outside of the grid never are.
+    pub fn contains(&self, position: impl Into<Position>) -> bool {
+        let position = position.into();
+        self.in_bounds(position) && {
+            let (word, bit) = self.bit(position);
+            self.words[word] & bit != 0
+        }
+    }
+
+    /// Adds a position. Returns whether it was not in the set before.
+    ///
+    /// # Panics
+    /// If the position lies outside of the grid.
+    pub fn insert(&mut self, position: impl Into<Position>) -> bool {
+        let position = position.into();
+        assert_in_bounds(position, self.width, self.height);
+        let (word, bit) = self.bit(position);
+        set_bit(&mut self.words, &mut self.touched, word, bit)
+    }
+
+    /// Removes a position. Returns whether it was in the set.
+    pub fn remove(&mut self, position: impl Into<Position>) -> bool {
+        let position = position.into();
+        if !self.in_bounds(position) {
+            return false;
+        }
+        let (word, bit) = self.bit(position);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    /// Removes all positions.
+    pub fn clear(&mut self) {
+        clear_words(&mut self.words, &mut self.touched);
+    }
+
+    /// The number of positions in the set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The positions in the set, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Position> + '_ {
+        let width = self.width;
+        set_bits(&self.words).map(move |index| (index / width, index % width))
+    }
+
+    /// The word and the mask of the bit of a position within the grid.
+    fn bit(&self, (row, col): Position) -> (usize, u64) {
+        let index = row * self.width + col;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+}
+
+/// A set of `(position, direction)` states in a `width` by `height` grid, with one bit per
+/// direction of every cell.
+#[derive(Clone, Debug)]
+pub struct DirectionalBitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+    /// The indices of words that were empty before an insert since the last clear.
+    touched: Vec<usize>,
+}
+
+/// The number of bits each cell takes up, one per direction in [`Direction::ALL`].
+const CELL_BITS: usize = Direction::ALL.len();
+
+impl PartialEq for DirectionalBitGrid {
+    fn eq(&self, other: &Self) -> bool {
+        (self.width, self.height) == (other.width, other.height) && self.words == other.words
+    }
+}
+
+impl Eq for DirectionalBitGrid {}
+
+impl DirectionalBitGrid {
+    /// Creates an empty set.
+    pub fn new(width: usize, height: usize) -> Self {
+        DirectionalBitGrid {
+            width,
+            height,
+            words: vec![0; (width * height * CELL_BITS).div_ceil(WORD_BITS)],
+            touched: vec![],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a position lies within the grid.
+    pub fn in_bounds(&self, position: impl Into<Position>) -> bool {
+        let (row, col) = position.into();
+        row < self.height && col < self.width
+    }
+
+    /// Whether a state is in the set. Positions outside of the grid never are.
+    pub fn contains(&self, position: impl Into<Position>, direction: Direction) -> bool {
+        let position = position.into();
+        self.in_bounds(position) && {
+            let (word, bit) = self.bit(position, direction);
+            self.words[word] & bit != 0
+        }
+    }
+
+    /// Whether the position is in the set with any direction.
+    pub fn contains_any(&self, position: impl Into<Position>) -> bool {
+        let position = position.into();
+        self.in_bounds(position) && {
+            let (word, shift) = self.cell(position);
+            (self.words[word] >> shift) & CELL_MASK != 0
+        }
+    }
+
+    /// Adds a state. Returns whether it was not in the set before.
+    ///
+    /// # Panics
+    /// If the position lies outside of the grid.
+    pub fn insert(&mut self, position: impl Into<Position>, direction: Direction) -> bool {
+        let position = position.into();
+        assert_in_bounds(position, self.width, self.height);
+        let (word, bit) = self.bit(position, direction);
+        set_bit(&mut self.words, &mut self.touched, word, bit)
+    }
+
+    /// Removes a state. Returns whether it was in the set.
+    pub fn remove(&mut self, position: impl Into<Position>, direction: Direction) -> bool {
+        let position = position.into();
+        if !self.in_bounds(position) {
+            return false;
+        }
+        let (word, bit) = self.bit(position, direction);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    /// Removes all states.
+    pub fn clear(&mut self) {
+        clear_words(&mut self.words, &mut self.touched);
+    }
+
+    /// The number of states in the set.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// The states in the set, row by row and in the order of [`Direction::ALL`] within a cell.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, Direction)> + '_ {
+        let width = self.width;
+        set_bits(&self.words).map(move |index| {
+            let cell = index / CELL_BITS;
+            (
+                (cell / width, cell % width),
+                Direction::ALL[index % CELL_BITS],
+            )
+        })
+    }
+
+    /// The positions that are in the set with any direction.
+    pub fn positions(&self) -> BitGrid {
+        let mut positions = BitGrid::new(self.width, self.height);
+        for (position, _) in self.iter() {
+            positions.insert(position);
+        }
+        positions
+    }
+
+    /// The word and the offset of the first bit of a cell.
+    fn cell(&self, (row, col): Position) -> (usize, usize) {
+        let index = (row * self.width + col) * CELL_BITS;
+        (index / WORD_BITS, index % WORD_BITS)
+    }
+
+    fn bit(&self, position: Position, direction: Direction) -> (usize, u64) {
+        let (word, shift) = self.cell(position);
+        (word, 1 << (shift + direction.index()))
+    }
+}
+
+/// The bits of a whole cell, before shifting.
+const CELL_MASK: u64 = (1 << CELL_BITS) - 1;
+
+/// Sets a bit, remembering the word if it was empty before. Once as many words were touched as
+/// there are, `clear` empties all of them anyway. Returns whether the bit was unset.
+fn set_bit(words: &mut [u64], touched: &mut Vec<usize>, word: usize, bit: u64) -> bool {
+    if words[word] == 0 && touched.len() < words.len() {
+        touched.push(word);
+    }
+    let added = words[word] & bit == 0;
+    words[word] |= bit;
+    added
+}
+
+/// Empties all touched words, or all words if that is cheaper.
+fn clear_words(words: &mut [u64], touched: &mut Vec<usize>) {
+    if touched.len() >= words.len() {
+        words.fill(0);
+    } else {
+        for &word in touched.iter() {
+            words[word] = 0;
+        }
+    }
+    touched.clear();
+}
+
+fn assert_in_bounds((row, col): Position, width: usize, height: usize) {
+    assert!(
+        row < height && col < width,
+        "position {:?} is outside of the {width}x{height} grid",
+        (row, col)
+    );
+}
+
+/// The indices of all set bits, in ascending order.
+fn set_bits(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(i, &word)| {
+        std::iter::successors((word != 0).then_some(word), |&rest| {
+            let rest = rest & (rest - 1);
+            (rest != 0).then_some(rest)
+        })
+        .map(move |rest| i * WORD_BITS + rest.trailing_zeros() as usize)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+
+    #[test]
+    fn sets_and_clears_bits() {
+        let mut set = BitGrid::new(10, 7);
+        assert!(set.is_empty());
+        assert!(set.insert((0, 0)));
+        assert!(set.insert((6, 9)));
+        assert!(set.insert(Point::new(3, 4)));
+        assert!(!set.insert((4, 3)));
+
+        assert!(set.contains((4, 3)));
+        assert!(!set.contains((3, 4)));
+        assert!(!set.contains((7, 0)));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 0), (4, 3), (6, 9)]);
+
+        assert!(set.remove((0, 0)));
+        assert!(!set.remove((0, 0)));
+        assert!(!set.remove((100, 100)));
+        set.clear();
+        assert!(set.is_empty());
+
+        // once every word was touched, clearing falls back to emptying all of them.
+        for position in (0..7).flat_map(|row| (0..10).map(move |col| (row, col))) {
+            set.insert(position);
+        }
+        assert_eq!(set.len(), 70);
+        set.clear();
+        assert!(set.is_empty());
+        assert!(set.insert((6, 9)));
+        assert_eq!(set, {
+            let mut other = BitGrid::new(10, 7);
+            other.insert((6, 9));
+            other
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 3x2 grid")]
+    fn rejects_positions_outside_of_the_grid() {
+        BitGrid::new(3, 2).insert((2, 0));
+    }
+
+    #[test]
+    fn builds_from_grids() {
+        let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+        let walls = BitGrid::from_grid(&grid, |&c| c == '#');
+        assert_eq!(walls.iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn tracks_directions() {
+        let mut states = DirectionalBitGrid::new(5, 5);
+        assert!(states.insert((2, 3), Direction::North));
+        assert!(states.insert((2, 3), Direction::NorthWest));
+        assert!(states.insert((4, 4), Direction::East));
+        assert!(!states.insert((2, 3), Direction::North));
+
+        assert!(states.contains((2, 3), Direction::NorthWest));
+        assert!(!states.contains((2, 3), Direction::South));
+        assert!(states.contains_any((4, 4)));
+        assert!(!states.contains_any((4, 3)));
+        assert_eq!(states.len(), 3);
+        assert_eq!(
+            states.iter().collect::<Vec<_>>(),
+            vec![
+                ((2, 3), Direction::North),
+                ((2, 3), Direction::NorthWest),
+                ((4, 4), Direction::East)
+            ]
+        );
+        assert_eq!(states.positions().len(), 2);
+
+        assert!(states.remove((2, 3), Direction::North));
+        assert!(states.contains_any((2, 3)));
+        states.clear();
+        assert!(states.is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+/// Cycle detection for sequences of states, e.g. the steps of a simulation.
+///
+/// Sequences are iterators. An iterator that ends has no cycle. [`floyd`] and [`brent`] use
+/// constant memory, but need to restart the sequence, which requires a `Clone` iterator; build
+/// one from a step function with [`iterate`]. [`hashed`] and [`fast_forward`] remember every
+/// state instead.
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states `x0, x1, ...`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The index of the earliest state equal to the state at index `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// The infinite sequence `initial, step(initial), step(step(initial)), ...`.
+pub fn iterate<S, F>(initial: S, step: F) -> impl Iterator<Item = S> + Clone
+where
+    S: Clone,
+    F: Fn(&S) -> S + Clone,
+{
+    std::iter::successors(Some(initial), move |state| Some(step(state)))
+}
+
+/// Floyd's tortoise and hare algorithm.
+pub fn floyd<S, I>(states: I) -> Option<Cycle>
+where
+    S: PartialEq,
+    I: Iterator<Item = S> + Clone,
+{
+    // the tortoise walks at single speed, the hare at double speed, until they meet inside the
+    // cycle at some index that is a multiple of the cycle length.
+    let mut tortoises = states.clone();
+    let mut hares = states.clone();
+    let mut tortoise = tortoises.nth(1)?;
+    let mut hare = hares.nth(2)?;
+    while tortoise != hare {
+        tortoise = tortoises.next()?;
+        hare = hares.nth(1)?;
+    }
+
+    // a tortoise from the start and the hare at single speed meet at the start of the cycle.
+    let mut tortoises = states;
+    tortoise = tortoises.next()?;
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = tortoises.next()?;
+        hare = hares.next()?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    while tortoises.next()? != tortoise {
+        length += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// Brent's algorithm, which usually needs fewer steps than [`floyd`].
+pub fn brent<S, I>(states: I) -> Option<Cycle>
+where
+    S: PartialEq,
+    I: Iterator<Item = S> + Clone,
+{
+    // the tortoise teleports to the hare whenever the hare has taken a power of two steps since
+    // the last teleport, until the hare finds it.
+    let mut hares = states.clone();
+    let mut tortoise = hares.next()?;
+    let mut hare = hares.next()?;
+    let (mut power, mut length) = (1, 1);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare;
+            power *= 2;
+            length = 0;
+        }
+        hare = hares.next()?;
+        length += 1;
+    }
+
+    // with the hare `length` states ahead, both meet at the start of the cycle.
+    let mut tortoises = states.clone();
+    let mut hares = states;
+    let mut tortoise = tortoises.next()?;
+    let mut hare = hares.nth(length)?;
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = tortoises.next()?;
+        hare = hares.next()?;
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// Finds the first repeated state by remembering the index of every state.
+pub fn hashed<S, I>(states: I) -> Option<Cycle>
+where
+    S: Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashMap::new();
+    for (i, state) in states.into_iter().enumerate() {
+        match seen.entry(state) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                return Some(Cycle {
+                    start,
+                    length: i - start,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+            }
+        }
+    }
+    None
+}
+
+/// The state at index `n`. Once the sequence repeats, the remaining steps are skipped using
+/// the period, so this works for `n` far beyond what could be simulated.
+///
+/// ```ignore
+/// let platform = fast_forward(iterate(platform, spin), 1_000_000_000)?;
+/// ```
+pub fn fast_forward<S, I>(states: I, n: usize) -> Option<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    for (i, state) in states.into_iter().enumerate() {
+        if i == n {
+            return Some(state);
+        }
+        match seen.entry(state.clone()) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let cycle = Cycle {
+                    start,
+                    length: i - start,
+                };
+                return history.get(cycle.equivalent_step(n)).cloned();
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+            }
+        }
+        history.push(state);
+    }
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    /// `x -> x² + 1 mod 255` from 3 runs into a cycle after a few steps.
+    fn sequence() -> impl Iterator<Item = u32> + Clone {
+        iterate(3, |x| (x * x + 1) % 255)
+    }
+
+    fn expected() -> Cycle {
+        let states: Vec<u32> = sequence().take(100).collect();
+        let start = (0..)
+            .find(|&i| states[i + 1..].contains(&states[i]))
+            .unwrap();
+        let length = states[start + 1..]
+            .iter()
+            .position(|&s| s == states[start])
+            .unwrap()
+            + 1;
+        Cycle { start, length }
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = expected();
+        assert!(expected.start > 0 && expected.length > 1);
+        assert_eq!(floyd(sequence()), Some(expected));
+        assert_eq!(brent(sequence()), Some(expected));
+        assert_eq!(hashed(sequence()), Some(expected));
+    }
+
+    #[test]
+    fn detects_cycles_of_fixed_points() {
+        let cycle = Some(Cycle {
+            start: 2,
+            length: 1,
+        });
+        let states = || [5, 4, 0, 0, 0].into_iter().chain(std::iter::repeat(0));
+        assert_eq!(floyd(states()), cycle);
+        assert_eq!(brent(states()), cycle);
+        assert_eq!(hashed(states()), cycle);
+        assert_eq!(
+            brent(iterate(7, |&x| x)),
+            Some(Cycle {
+                start: 0,
+                length: 1
+            })
+        );
+    }
+
+    #[test]
+    fn finds_no_cycles_in_finite_sequences() {
+        assert_eq!(floyd(1..10), None);
+        assert_eq!(brent(1..10), None);
+        assert_eq!(hashed(1..10), None);
+        assert_eq!(brent(std::iter::empty::<u8>()), None);
+    }
+
+    #[test]
+    fn fast_forwards() {
+        let n = 1_000_000_007;
+        let cycle = expected();
+        let state = sequence().nth(cycle.equivalent_step(n));
+        assert_eq!(fast_forward(sequence(), n), state);
+        assert_eq!(fast_forward(sequence(), 2), sequence().nth(2));
+        assert_eq!(fast_forward(0..5, 3), Some(3));
+        assert_eq!(fast_forward(0..5, 7), None);
+    }
+}
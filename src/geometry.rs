@@ -0,0 +1,353 @@
+/// Points, vectors and directions on a two-dimensional grid.
+///
+/// `x` grows to the right and `y` grows downwards, so a [`Point`] corresponds to the grid
+/// position `(row, column) = (y, x)`.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::grid::Position;
+
+/// A position with non-negative coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// A signed offset between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+
+    /// Adds a vector, returning `None` if a coordinate would become negative.
+    pub fn checked_add(self, vector: Vector) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(vector.x)?,
+            y: self.y.checked_add_signed(vector.y)?,
+        })
+    }
+
+    /// Adds a vector, wrapping around at the boundary of `usize`.
+    ///
+    /// Points that would become negative end up far outside of any grid, so the result can be
+    /// bounds-checked once instead of checking the addition.
+    pub fn wrapping_add(self, vector: Vector) -> Point {
+        Point {
+            x: self.x.wrapping_add_signed(vector.x),
+            y: self.y.wrapping_add_signed(vector.y),
+        }
+    }
+
+    /// Adds a vector on a `width` by `height` area whose edges wrap around.
+    ///
+    /// Panics if the area is empty, as there is no point to wrap to.
+    pub fn add_modulo(self, vector: Vector, width: usize, height: usize) -> Point {
+        assert!(
+            width > 0 && height > 0,
+            "can not wrap around an empty {width}x{height} area"
+        );
+        let wrap = |position: usize, offset: isize, size: usize| {
+            (position as i128 + offset as i128).rem_euclid(size as i128) as usize
+        };
+        Point {
+            x: wrap(self.x, vector.x, width),
+            y: wrap(self.y, vector.y, height),
+        }
+    }
+
+    /// The point one step away in a direction, if it is not negative.
+    pub fn step(self, direction: Direction) -> Option<Point> {
+        self.checked_add(direction.vector())
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<Position> for Point {
+    /// Converts a grid position `(row, column)`.
+    fn from((row, col): Position) -> Self {
+        Point { x: col, y: row }
+    }
+}
+
+impl From<Point> for Position {
+    /// Converts to a grid position `(row, column)`.
+    fn from(point: Point) -> Self {
+        (point.y, point.x)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    /// The vector pointing from `other` to `self`.
+    fn sub(self, other: Point) -> Vector {
+        let signed = |n: usize| isize::try_from(n).expect("coordinates fit into isize");
+        Vector {
+            x: signed(self.x) - signed(other.x),
+            y: signed(self.y) - signed(other.y),
+        }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vector { x, y }
+    }
+
+    pub fn manhattan_length(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// Adds two vectors, returning `None` on overflow.
+    pub fn checked_add(self, other: Vector) -> Option<Vector> {
+        Some(Vector::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    /// Scales the vector, returning `None` on overflow.
+    pub fn checked_mul(self, factor: isize) -> Option<Vector> {
+        Some(Vector::new(
+            self.x.checked_mul(factor)?,
+            self.y.checked_mul(factor)?,
+        ))
+    }
+}
+
+// like the integer operators, `+`, `-` and `*` panic on overflow in debug builds and wrap in
+// release builds. Use `checked_add` and `checked_mul` where the result can overflow.
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self + -other
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: isize) -> Vector {
+        Vector::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+/// One of the eight compass directions, with north pointing up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise starting with north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions, clockwise starting with north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The position of the direction in [`Direction::ALL`], for direction-indexed tables.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The unit step in this direction. Diagonal steps move along both axes.
+    pub fn vector(self) -> Vector {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        Vector::new(x, y)
+    }
+
+    /// Turns by 90 degrees clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Turns by 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Parses the arrows `^`, `>`, `v` and `<`, as used for guards and robots.
+    pub fn from_arrow(ch: char) -> Option<Direction> {
+        match ch {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Rotates clockwise in steps of 45 degrees.
+    fn rotate(self, steps: usize) -> Direction {
+        Direction::ALL[(self.index() + steps) % Direction::ALL.len()]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_vectors_to_points() {
+        let point = Point::new(1, 2);
+        assert_eq!(
+            point.checked_add(Vector::new(-1, 3)),
+            Some(Point::new(0, 5))
+        );
+        assert_eq!(point.checked_add(Vector::new(-2, 0)), None);
+        assert_eq!(point.wrapping_add(Vector::new(-2, 0)).x, usize::MAX);
+        assert_eq!(
+            point.add_modulo(Vector::new(-2, 100) * 3, 11, 7),
+            Point::new(6, 1)
+        );
+        assert_eq!(Point::new(4, 0) - point, Vector::new(3, -2));
+        assert_eq!(point.manhattan_distance(Point::new(4, 0)), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty 0x7 area")]
+    fn rejects_wrapping_around_empty_areas() {
+        Point::new(0, 0).add_modulo(Vector::new(1, 1), 0, 7);
+    }
+
+    #[test]
+    fn checks_vector_overflow() {
+        let vector = Vector::new(3, -4);
+        assert_eq!(
+            vector.checked_add(Vector::new(-3, 1)),
+            Some(Vector::new(0, -3))
+        );
+        assert_eq!(vector.checked_add(Vector::new(isize::MAX, 0)), None);
+        assert_eq!(vector.checked_mul(-2), Some(Vector::new(-6, 8)));
+        assert_eq!(vector.checked_mul(isize::MAX), None);
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let point = Point::from((2, 5));
+        assert_eq!(point, Point::new(5, 2));
+        assert_eq!(Position::from(point), (2, 5));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthWest.turn_right(), Direction::NorthEast);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+        assert!(Direction::SouthEast.is_diagonal());
+        assert!(!Direction::West.is_diagonal());
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().vector(), -direction.vector());
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+        assert_eq!(
+            Point::new(3, 3).step(Direction::NorthWest),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(Point::new(0, 3).step(Direction::West), None);
+    }
+
+    #[test]
+    fn parses_arrows() {
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::South));
+        assert_eq!(Direction::from_arrow('x'), None);
+    }
+}
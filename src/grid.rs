@@ -0,0 +1,506 @@
+/// A two-dimensional grid, as used by many puzzles whose input is a map of characters.
+///
+/// Positions are `(row, column)` pairs, with `(0, 0)` in the top left corner.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::{Direction, Point};
+
+/// A position in a grid as `(row, column)`.
+pub type Position = (usize, usize);
+
+/// The offsets of the four orthogonal neighbours, clockwise starting with up.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// The offsets of all eight neighbours, clockwise starting with up.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The input has no cells.
+    Empty,
+    /// A line has a different length than the first line.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell mapping rejected a character.
+    InvalidCell { row: usize, col: usize, ch: char },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid is empty."),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} has {found} cells, expected {expected}.",
+                row + 1
+            ),
+            GridError::InvalidCell { row, col, ch } => write!(
+                f,
+                "invalid cell `{ch}` at line {}, column {}.",
+                row + 1,
+                col + 1
+            ),
+        }
+    }
+}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a grid from lines of text, mapping each character to a cell.
+    /// The mapping returns `None` for characters that are not allowed.
+    ///
+    /// ```ignore
+    /// let heights = Grid::parse(input, |c| c.to_digit(10))?;
+    /// ```
+    pub fn parse(input: &str, mut map: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                cells.push(map(ch).ok_or(GridError::InvalidCell { row, col, ch })?);
+            }
+
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        row,
+                        expected,
+                        found,
+                    })
+                }
+                _ => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a position lies within the grid.
+    pub fn contains(&self, position: impl Into<Position>) -> bool {
+        let (row, col) = position.into();
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: impl Into<Position>) -> Option<&T> {
+        let (row, col) = position.into();
+        self.contains((row, col))
+            .then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, position: impl Into<Position>) -> Option<&mut T> {
+        let (row, col) = position.into();
+        self.contains((row, col))
+            .then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// Moves a position by a signed `(rows, columns)` offset. Returns `None` if the result leaves the grid.
+    pub fn offset(&self, (row, col): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Moves a point one step in a direction. Returns `None` if the result leaves the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point.step(direction).filter(|&next| self.contains(next))
+    }
+
+    /// The up to four orthogonal neighbours of a position.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// The up to eight neighbours of a position, including diagonal ones.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_DIRECTIONS
+            .into_iter()
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// The positions from `start` (inclusive) in steps of `delta`, until leaving the grid.
+    pub fn ray(
+        &self,
+        start: Position,
+        delta: (isize, isize),
+    ) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.contains(start).then_some(start), move |&position| {
+            self.offset(position, delta)
+        })
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, that matches the predicate.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find_map(|(position, cell)| predicate(cell).then_some(position))
+    }
+
+    /// The position of the first cell, row by row, that equals `value`.
+    pub fn position_of(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// The positions of all cells that equal `value`.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(position, cell)| (cell == value).then_some(position))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.width,
+            "column {col} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The diagonals running from the top left to the bottom right, starting with the bottom left one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((1..self.width).map(|col| (0, col)))
+            .filter(|&start| self.contains(start));
+        starts.map(|start| self.ray(start, (1, 1)).map(|position| &self[position]))
+    }
+
+    /// The diagonals running from the top right to the bottom left, starting with the top left one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        // a grid without columns has no diagonals, and no last column to start them in.
+        let last_col = self.width.checked_sub(1);
+        let starts = (0..self.width)
+            .map(|col| (0, col))
+            .chain((1..self.height).filter_map(move |row| Some((row, last_col?))))
+            .filter(|&start| self.contains(start));
+        starts.map(|start| self.ray(start, (1, -1)).map(|position| &self[position]))
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(row, col)| (col, row))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(row, col)| {
+            (self.height - 1 - col, row)
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(row, col)| {
+            (col, self.width - 1 - row)
+        })
+    }
+
+    /// Builds a grid of the given size, taking each cell from the position `source` maps it to.
+    fn rearrange(&self, width: usize, height: usize, source: impl Fn(Position) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("position {position:?} is outside of the {width}x{height} grid")
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        &self[Position::from(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        &mut self[Position::from(point)]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, Some)
+    }
+}
+
+/// Renders the cells of each row next to each other, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits[(1, 0)], 3);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10)),
+            Err(GridError::InvalidCell {
+                row: 1,
+                col: 1,
+                ch: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = grid();
+        assert_eq!(grid.position_of(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c > 'c'), Some((1, 0)));
+        assert_eq!(grid.positions_of(&'z').count(), 0);
+    }
+
+    #[test]
+    fn indexes_by_points() {
+        let grid = grid();
+        let point = Point::new(2, 0);
+        assert_eq!(grid[point], 'c');
+        assert_eq!(grid.step(point, Direction::South), Some(Point::new(2, 1)));
+        assert_eq!(grid.step(point, Direction::East), None);
+        assert_eq!(grid.step(point, Direction::North), None);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        let collect = |cells: &mut dyn Iterator<Item = &char>| cells.collect::<String>();
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(collect(&mut grid.column(2)), "cf");
+        assert_eq!(
+            grid.diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["d", "ae", "bf", "c"]
+        );
+        assert_eq!(
+            grid.anti_diagonals()
+                .map(|mut d| collect(&mut d))
+                .collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"]
+        );
+        assert_eq!(
+            grid.ray((1, 2), (0, -1))
+                .map(|p| grid[p])
+                .collect::<String>(),
+            "fed"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of the 3x2 grid")]
+    fn rejects_columns_outside_of_the_grid() {
+        grid().column(3);
+    }
+
+    #[test]
+    fn iterates_lines_of_empty_grids() {
+        for grid in [Grid::new(0, 3, 'x'), Grid::new(3, 0, 'x')] {
+            assert_eq!(grid.columns().count(), grid.width());
+            assert_eq!(grid.diagonals().count(), 0);
+            assert_eq!(grid.anti_diagonals().count(), 0);
+        }
+    }
+
+    #[test]
+    fn rearranges_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
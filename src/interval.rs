@@ -0,0 +1,335 @@
+/// Sets and maps of half-open ranges `start..end`, backed by B-trees.
+///
+/// Unlike vectors of ranges, insertion, removal and lookup take logarithmic time in the
+/// number of stored intervals.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+use std::ops::{Range, Sub};
+
+/// A set of values, stored as disjoint intervals. Overlapping or touching intervals are merged.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    /// The end of each interval, by its start.
+    intervals: BTreeMap<T, T>,
+    /// The starts of intervals, by their length. Allows finding gaps of a certain size.
+    by_length: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: BTreeMap::new(),
+            by_length: BTreeMap::new(),
+        }
+    }
+
+    /// The number of disjoint intervals.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Adds all values in `range`, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let Range { mut start, mut end } = range;
+
+        if let Some((&s, &e)) = self.intervals.range(..=start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+                self.take(s);
+            }
+        }
+        while let Some((&s, &e)) = self.intervals.range(start..=end).next() {
+            end = end.max(e);
+            self.take(s);
+        }
+        self.put(start, end);
+    }
+
+    /// Removes all values in `range`, splitting the intervals it partly covers.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&s, &e)) = self.intervals.range(..range.start).next_back() {
+            if e > range.start {
+                self.take(s);
+                self.put(s, range.start);
+                if e > range.end {
+                    self.put(range.end, e);
+                }
+            }
+        }
+        while let Some((&s, &e)) = self.intervals.range(range.start..range.end).next() {
+            self.take(s);
+            if e > range.end {
+                self.put(range.end, e);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.interval_containing(value).is_some()
+    }
+
+    /// The interval that contains `value`.
+    pub fn interval_containing(&self, value: T) -> Option<Range<T>> {
+        let (&start, &end) = self.intervals.range(..=value).next_back()?;
+        (value < end).then_some(start..end)
+    }
+
+    /// The first interval, in order, that is at least `length` long.
+    ///
+    /// This takes logarithmic time for every distinct interval length of at least `length`, so
+    /// it is fast when lengths are small, e.g. free space on a disk.
+    pub fn first_fit(&self, length: T) -> Option<Range<T>> {
+        let start = self
+            .by_length
+            .range(length..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()?;
+        Some(*start..self.intervals[start])
+    }
+
+    /// The intervals in order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The ranges between consecutive intervals, in order.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .map(|(before, after)| before.end..after.start)
+    }
+
+    fn put(&mut self, start: T, end: T) {
+        if start < end {
+            self.intervals.insert(start, end);
+            self.by_length.entry(end - start).or_default().insert(start);
+        }
+    }
+
+    fn take(&mut self, start: T) {
+        let end = self.intervals.remove(&start).expect("interval exists");
+        let length = end - start;
+        let starts = self.by_length.get_mut(&length).expect("length is indexed");
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_length.remove(&length);
+        }
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A map from disjoint intervals to values. Inserting over existing intervals replaces the
+/// overlapped parts; touching intervals with equal values are merged.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalMap<T, V> {
+    /// The end and value of each interval, by its start.
+    entries: BTreeMap<T, (T, V)>,
+}
+
+impl<T: Copy + Ord, V: Clone + PartialEq> IntervalMap<T, V> {
+    pub fn new() -> Self {
+        IntervalMap {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// The number of disjoint intervals.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maps every value in `range` to `value`.
+    pub fn insert(&mut self, range: Range<T>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+        self.remove(range.clone());
+        let Range { mut start, mut end } = range;
+
+        if let Some((&s, (e, v))) = self.entries.range(..start).next_back() {
+            if *e == start && *v == value {
+                start = s;
+            }
+        }
+        if let Some((e, v)) = self.entries.get(&end) {
+            if *v == value {
+                let e = *e;
+                self.entries.remove(&end);
+                end = e;
+            }
+        }
+        self.entries.insert(start, (end, value));
+    }
+
+    /// Unmaps all values in `range`, splitting the intervals it partly covers.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&s, (e, v))) = self.entries.range(..range.start).next_back() {
+            let (e, v) = (*e, v.clone());
+            if e > range.start {
+                self.entries.insert(s, (range.start, v.clone()));
+                if e > range.end {
+                    self.entries.insert(range.end, (e, v));
+                }
+            }
+        }
+        while let Some((&s, _)) = self.entries.range(range.start..range.end).next() {
+            let (e, v) = self.entries.remove(&s).expect("interval exists");
+            if e > range.end {
+                self.entries.insert(range.end, (e, v));
+            }
+        }
+    }
+
+    pub fn get(&self, point: T) -> Option<&V> {
+        self.get_interval(point).map(|(_, value)| value)
+    }
+
+    /// The interval containing `point` and its value.
+    pub fn get_interval(&self, point: T) -> Option<(Range<T>, &V)> {
+        let (&start, (end, value)) = self.entries.range(..=point).next_back()?;
+        (point < *end).then_some((start..*end, value))
+    }
+
+    /// The intervals and their values in order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range<T>, &V)> + '_ {
+        self.entries
+            .iter()
+            .map(|(&start, (end, value))| (start..*end, value))
+    }
+}
+
+impl<T: Copy + Ord, V: Clone + PartialEq> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord + Debug, V: Clone + PartialEq + Debug> Debug for IntervalMap<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Copy + Ord + Sub<Output = T>>(set: &IntervalSet<T>) -> Vec<Range<T>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn merges_intervals() {
+        let mut set: IntervalSet<u32> = [5..8, 1..3, 10..12].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..3, 5..8, 10..12]);
+
+        // touching intervals merge, as do all intervals that are covered.
+        set.insert(3..5);
+        assert_eq!(ranges(&set), vec![1..8, 10..12]);
+        set.insert(0..20);
+        assert_eq!(ranges(&set), vec![0..20]);
+        set.insert(4..4);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn removes_intervals() {
+        let mut set: IntervalSet<i64> = [0..10, 20..30].into_iter().collect();
+        set.remove(3..5);
+        assert_eq!(ranges(&set), vec![0..3, 5..10, 20..30]);
+        set.remove(8..22);
+        assert_eq!(ranges(&set), vec![0..3, 5..8, 22..30]);
+        set.remove(-5..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn finds_values_and_gaps() {
+        let set: IntervalSet<u32> = [0..2, 4..9, 12..14].into_iter().collect();
+        assert!(set.contains(4));
+        assert!(!set.contains(9));
+        assert_eq!(set.interval_containing(7), Some(4..9));
+        assert_eq!(set.gaps().collect::<Vec<_>>(), vec![2..4, 9..12]);
+    }
+
+    #[test]
+    fn finds_first_fit() {
+        let mut set: IntervalSet<u32> = [0..2, 4..9, 12..14, 20..25].into_iter().collect();
+        assert_eq!(set.first_fit(2), Some(0..2));
+        assert_eq!(set.first_fit(3), Some(4..9));
+        assert_eq!(set.first_fit(6), None);
+
+        set.remove(4..7);
+        assert_eq!(set.first_fit(3), Some(20..25));
+        set.insert(7..10);
+        assert_eq!(set.first_fit(3), Some(7..10));
+    }
+
+    #[test]
+    fn maps_intervals() {
+        let mut map = IntervalMap::new();
+        map.insert(0..10, 'a');
+        map.insert(3..5, 'b');
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(0..3, &'a'), (3..5, &'b'), (5..10, &'a')]
+        );
+        assert_eq!(map.get(4), Some(&'b'));
+        assert_eq!(map.get_interval(7), Some((5..10, &'a')));
+        assert_eq!(map.get(10), None);
+
+        // touching intervals with equal values merge again.
+        map.insert(3..5, 'a');
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![(0..10, &'a')]);
+
+        map.remove(2..8);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(0..2, &'a'), (8..10, &'a')]
+        );
+        assert_eq!(map.len(), 2);
+    }
+}
@@ -1,3 +1,14 @@
+pub mod bitset;
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod region;
+pub mod search;
 pub mod template;
+pub mod union_find;
 
 // Use this file to add helper functions and additional modules.
@@ -1,14 +1,17 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, generate, read, scaffold, solve, status, time, tui, watch, CommandError,
+};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use std::path::PathBuf;
+
+    use advent_of_code::template::visualize::{self, Settings};
+    use advent_of_code::template::{Day, InputSource};
 
     pub enum AppArguments {
         Download {
@@ -27,6 +30,12 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: InputSource,
+            watch: bool,
+            test: bool,
+            compare: bool,
+            verbose: bool,
+            visualize: Option<Settings>,
         },
         All {
             release: bool,
@@ -36,6 +45,23 @@ mod args {
             day: Option<Day>,
             store: bool,
         },
+        Scale {
+            day: Day,
+            steps: u32,
+            seed: u64,
+            export: Option<PathBuf>,
+        },
+        Status {
+            skip_tests: bool,
+        },
+        Tui,
+        Generate {
+            day: Option<Day>,
+            size: Option<usize>,
+            seed: u64,
+            out: Option<PathBuf>,
+            overwrite: bool,
+        },
         #[cfg(feature = "today")]
         Today,
     }
@@ -47,6 +73,14 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("time") if args.contains("--scale") => AppArguments::Scale {
+                steps: args.opt_value_from_str("--steps")?.unwrap_or(5),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+                export: args.opt_value_from_str("--export")?,
+                day: args
+                    .opt_free_from_str()?
+                    .ok_or("`--scale` requires a day.")?,
+            },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
@@ -57,6 +91,17 @@ mod args {
                     store,
                 }
             }
+            Some("status") => AppArguments::Status {
+                skip_tests: args.contains("--skip-tests"),
+            },
+            Some("tui") => AppArguments::Tui,
+            Some("gen") => AppArguments::Generate {
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+                out: args.opt_value_from_str("--out")?,
+                overwrite: args.contains("--overwrite"),
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -73,17 +118,17 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                input: parse_input_source(&mut args)?,
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
+                compare: args.contains("--compare"),
+                verbose: args.contains("--verbose"),
+                visualize: parse_visualize(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command: {x}").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -91,54 +136,166 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
+        if let AppArguments::Solve {
+            submit,
+            input,
+            watch,
+            test,
+            dhat,
+            compare,
+            verbose,
+            visualize,
+            ..
+        } = &app_args
+        {
+            if visualize.is_some() && (*watch || *compare) {
+                return Err(
+                    "`--visualize` can not be combined with `--watch` or `--compare`.".into(),
+                );
+            }
+            if *verbose && (*watch || *compare) {
+                return Err(
+                    "`--verbose` can not be combined with `--watch` or `--compare`.".into(),
+                );
+            }
+            if *compare && (submit.is_some() || *watch || *dhat) {
+                return Err(
+                    "`--compare` can not be combined with `--submit`, `--watch` or `--dhat`."
+                        .into(),
+                );
+            }
+            if submit.is_some() && !input.is_puzzle() {
+                return Err(format!("cannot submit a result computed from {input}.").into());
+            }
+            if *watch && (submit.is_some() || *dhat) {
+                return Err("`--watch` can not be combined with `--submit` or `--dhat`.".into());
+            }
+            if *test && !*watch {
+                return Err("`--test` is only supported together with `--watch`.".into());
+            }
+        }
+
         Ok(app_args)
     }
+
+    fn parse_visualize(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Settings>, Box<dyn std::error::Error>> {
+        let format: Option<visualize::Format> = args.opt_value_from_str("--visualize")?;
+        let fps: Option<u32> = args.opt_value_from_str("--fps")?;
+
+        match (format, fps) {
+            (_, Some(0)) => Err("`--fps` expects a positive number.".into()),
+            (None, Some(_)) => Err("`--fps` is only supported together with `--visualize`.".into()),
+            (Some(format), fps) => Ok(Some(Settings {
+                format,
+                fps: fps.unwrap_or(Settings::DEFAULT_FPS),
+            })),
+            (None, None) => Ok(None),
+        }
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let example = args.contains("--example");
+        let example_part: Option<u8> = args.opt_value_from_str("--example-part")?;
+
+        match (path, example, example_part) {
+            (None, false, None) => Ok(InputSource::Puzzle),
+            (Some(path), false, None) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), false, None) => Ok(InputSource::File(path.into())),
+            (None, true, None) => Ok(InputSource::Example),
+            (None, false, Some(part)) => Ok(InputSource::ExamplePart(part)),
+            _ => Err("`--input`, `--example` and `--example-part` are mutually exclusive.".into()),
+        }
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    let result = match parse() {
+        Err(err) => Err(CommandError::Usage(err.to_string())),
+        Ok(args) => run(args),
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), CommandError> {
+    match args {
+        AppArguments::All { release } => all::handle(release),
+        AppArguments::Time { day, all, store } => time::handle(day, all, store),
+        AppArguments::Scale {
+            day,
+            steps,
+            seed,
+            export,
+        } => time::handle_scale(day, steps, seed, export.as_deref()),
+        AppArguments::Status { skip_tests } => status::handle(!skip_tests),
+        AppArguments::Tui => tui::handle(),
+        AppArguments::Generate {
+            day,
+            size,
+            seed,
+            out,
+            overwrite,
+        } => generate::handle(day, size, seed, out, overwrite),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(day, overwrite)?;
+            if download {
+                download::handle(day)?;
+            }
+            Ok(())
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            input,
+            watch,
+            test,
+            compare,
+            verbose,
+            visualize,
+        } => {
+            if watch {
+                watch::handle(day, release, test, &input)
+            } else {
+                solve::handle(
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    &input,
+                    compare,
+                    verbose,
+                    visualize.as_ref(),
+                )
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Day::today() {
+            Some(day) => {
+                scaffold::handle(day, false)?;
+                download::handle(day)?;
+                read::handle(day)
             }
+            None => Err(CommandError::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
-    };
+    }
 }
@@ -0,0 +1,510 @@
+/// Exact arithmetic: linear systems over the rationals, number theory and decimal digits.
+///
+/// Everything is overflow-checked and returns `None` (or [`SolveError::Overflow`]) instead of
+/// wrapping around in release builds.
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+
+use num_traits::{checked_pow, PrimInt, Signed};
+
+/* ------------------------------- Rationals -------------------------------- */
+
+/// An exact fraction in lowest terms, with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    /// Returns `None` if the denominator is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator)?;
+        let sign = denominator.signum();
+        Some(Rational {
+            numerator: sign.checked_mul(numerator / divisor)?,
+            denominator: sign.checked_mul(denominator / divisor)?,
+        })
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value as an integer, if it has no fractional part.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Option<Rational> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Option<Rational> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Option<Rational> {
+        // cancel crosswise first, which keeps intermediate values small.
+        let a = gcd(self.numerator, other.denominator)?;
+        let b = gcd(other.numerator, self.denominator)?;
+        Rational::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+
+    /// Returns `None` when dividing by zero.
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        self.checked_mul(Rational::new(other.denominator, other.numerator)?)
+    }
+
+    pub fn checked_neg(self) -> Option<Rational> {
+        Some(Rational {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // compare the integer parts, then the reciprocals of the fractional parts, as in a
+        // continued fraction. This never overflows, unlike cross multiplying.
+        let split = |r: Rational| {
+            (
+                r.numerator.div_euclid(r.denominator),
+                r.numerator.rem_euclid(r.denominator),
+            )
+        };
+        let (mut a, mut b) = (*self, *other);
+        loop {
+            let ((a_int, a_rem), (b_int, b_rem)) = (split(a), split(b));
+            match (a_int.cmp(&b_int), a_rem, b_rem) {
+                (Ordering::Equal, 0, 0) => return Ordering::Equal,
+                (Ordering::Equal, 0, _) => return Ordering::Less,
+                (Ordering::Equal, _, 0) => return Ordering::Greater,
+                // the larger fractional part has the smaller reciprocal, so swap sides.
+                (Ordering::Equal, _, _) => {
+                    (a, b) = (
+                        Rational {
+                            numerator: b.denominator,
+                            denominator: b_rem,
+                        },
+                        Rational {
+                            numerator: a.denominator,
+                            denominator: a_rem,
+                        },
+                    );
+                }
+                (unequal, _, _) => return unequal,
+            }
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_integer() {
+            Some(n) => write!(f, "{n}"),
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+/* ----------------------------- Linear systems ----------------------------- */
+
+/// An error which can be returned when solving a linear system.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The matrix is not square or does not match the right-hand side.
+    Shape,
+    /// The determinant is zero, so there is no solution or infinitely many.
+    Singular,
+    /// An intermediate value does not fit into an `i128`.
+    Overflow,
+}
+
+impl Error for SolveError {}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Shape => write!(f, "the system is not square."),
+            SolveError::Singular => write!(f, "the system has no unique solution."),
+            SolveError::Overflow => write!(f, "the system overflowed while solving."),
+        }
+    }
+}
+
+/// Solves `a * x = b` for a 2x2 matrix `a` with Cramer's rule.
+pub fn solve_2x2(a: [[i128; 2]; 2], b: [i128; 2]) -> Result<[Rational; 2], SolveError> {
+    let cross =
+        |p: i128, q: i128, r: i128, s: i128| p.checked_mul(q)?.checked_sub(r.checked_mul(s)?);
+    let det = cross(a[0][0], a[1][1], a[0][1], a[1][0]).ok_or(SolveError::Overflow)?;
+    if det == 0 {
+        return Err(SolveError::Singular);
+    }
+    let det_x = cross(b[0], a[1][1], a[0][1], b[1]).ok_or(SolveError::Overflow)?;
+    let det_y = cross(a[0][0], b[1], b[0], a[1][0]).ok_or(SolveError::Overflow)?;
+    Ok([
+        Rational::new(det_x, det).ok_or(SolveError::Overflow)?,
+        Rational::new(det_y, det).ok_or(SolveError::Overflow)?,
+    ])
+}
+
+/// Solves `a * x = b` for a square matrix `a`, given as rows, with Gauss-Jordan elimination.
+pub fn solve(a: &[Vec<i128>], b: &[i128]) -> Result<Vec<Rational>, SolveError> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        return Err(SolveError::Shape);
+    }
+
+    // the augmented matrix `[a | b]`.
+    let mut rows: Vec<Vec<Rational>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| row.iter().chain([&rhs]).map(|&v| v.into()).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|&row| !rows[row][col].is_zero())
+            .ok_or(SolveError::Singular)?;
+        rows.swap(col, pivot);
+
+        let pivot_row = rows[col].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == col || row[col].is_zero() {
+                continue;
+            }
+            let factor = row[col]
+                .checked_div(pivot_row[col])
+                .ok_or(SolveError::Overflow)?;
+            for (value, &pivot_value) in row.iter_mut().zip(&pivot_row).skip(col) {
+                *value = pivot_value
+                    .checked_mul(factor)
+                    .and_then(|v| value.checked_sub(v))
+                    .ok_or(SolveError::Overflow)?;
+            }
+        }
+    }
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| row[n].checked_div(row[i]).ok_or(SolveError::Overflow))
+        .collect()
+}
+
+/* ------------------------------ Number theory ----------------------------- */
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+/// Returns `None` if it does not fit, e.g. `gcd(i64::MIN, 0)`.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> Option<T> {
+    let (mut a, mut b) = (a, b);
+    while b != T::zero() {
+        // `checked_div` only fails for `MIN / -1`, whose remainder is zero.
+        let remainder = a
+            .checked_div(&b)
+            .map_or(T::zero(), |quotient| a - quotient * b);
+        (a, b) = (b, remainder);
+    }
+    if a < T::zero() {
+        T::zero().checked_sub(&a)
+    } else {
+        Some(a)
+    }
+}
+
+/// The least common multiple, or `None` if it overflows.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    let multiple = a.checked_div(&gcd(a, b)?)?.checked_mul(&b)?;
+    if multiple < T::zero() {
+        T::zero().checked_sub(&multiple)
+    } else {
+        Some(multiple)
+    }
+}
+
+/// The least common multiple of all numbers, e.g. the combined period of several cycles.
+/// The least common multiple of no numbers is 1.
+pub fn lcm_all<T: PrimInt>(numbers: &[T]) -> Option<T> {
+    numbers.iter().try_fold(T::one(), |acc, &n| lcm(acc, n))
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`, or `None` if any of them
+/// overflows.
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> Option<(T, T, T)> {
+    let step = |old: T, new: T, quotient: T| old.checked_sub(&quotient.checked_mul(&new)?);
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while r != T::zero() {
+        let quotient = old_r.checked_div(&r)?;
+        (old_r, r) = (r, step(old_r, r, quotient)?);
+        (old_x, x) = (x, step(old_x, x, quotient)?);
+        (old_y, y) = (y, step(old_y, y, quotient)?);
+    }
+    if old_r < T::zero() {
+        let negate = |n: T| T::zero().checked_sub(&n);
+        Some((negate(old_r)?, negate(old_x)?, negate(old_y)?))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// The inverse of `a` modulo `m` in `0..m`, if `a` and `m` are coprime.
+pub fn mod_inverse<T: PrimInt + Signed>(a: T, m: T) -> Option<T> {
+    if m <= T::zero() {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a % m, m)?;
+    if g != T::one() {
+        return None;
+    }
+    // `x % m` lies strictly between `-m` and `m`, so adding `m` cannot overflow.
+    let x = x % m;
+    Some(if x < T::zero() { x + m } else { x })
+}
+
+/// The Chinese remainder theorem: combines congruences `x ≡ residue (mod modulus)` into a
+/// single `(residue, modulus)`. Moduli need not be coprime; returns `None` if the congruences
+/// contradict each other or the combined modulus overflows.
+pub fn crt<T: PrimInt + Signed>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    congruences
+        .iter()
+        .try_fold((T::zero(), T::one()), |(r1, m1), &(r2, m2)| {
+            if m2 <= T::zero() {
+                return None;
+            }
+            let (g, p, _) = extended_gcd(m1, m2)?;
+            let difference = r2.checked_sub(&r1)? % m2;
+            if difference % g != T::zero() {
+                return None;
+            }
+
+            // x = r1 + m1 * k, where k solves m1 * k ≡ r2 - r1 (mod m2).
+            let step = m2 / g;
+            let k = (difference / g).checked_mul(&p)? % step;
+            let modulus = m1.checked_mul(&step)?;
+            let residue = r1.checked_add(&m1.checked_mul(&k)?)? % modulus;
+            if residue < T::zero() {
+                Some((residue + modulus, modulus))
+            } else {
+                Some((residue, modulus))
+            }
+        })
+}
+
+/* --------------------------------- Digits --------------------------------- */
+
+/// `10^exponent`, or `None` if it overflows.
+pub fn pow10<T: PrimInt>(exponent: u32) -> Option<T> {
+    checked_pow(T::from(10)?, usize::try_from(exponent).ok()?)
+}
+
+/// The number of decimal digits, ignoring the sign. Zero has one digit.
+pub fn digit_count<T: PrimInt>(n: T) -> u32 {
+    let ten = T::from(10).expect("10 fits into every integer type");
+    let mut n = n / ten;
+    let mut count = 1;
+    while n != T::zero() {
+        n = n / ten;
+        count += 1;
+    }
+    count
+}
+
+/// The decimal digits of a non-negative number, most significant first.
+pub fn digits<T: PrimInt>(n: T) -> Vec<u8> {
+    let ten = T::from(10).expect("10 fits into every integer type");
+    let mut digits = vec![];
+    let mut n = n;
+    loop {
+        digits.push((n % ten).to_u8().unwrap_or(0));
+        n = n / ten;
+        if n <= T::zero() {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+/// Splits off the last `low_digits` decimal digits: `split_digits(123456, 2)` is
+/// `(1234, 56)`. This cannot overflow, since it only divides by a positive power of ten.
+pub fn split_digits<T: PrimInt>(n: T, low_digits: u32) -> (T, T) {
+    match pow10::<T>(low_digits) {
+        Some(divisor) => (n / divisor, n % divisor),
+        // the divisor is larger than any number, so all digits are low digits.
+        None => (T::zero(), n),
+    }
+}
+
+/// Writes the digits of `b` after the digits of `a`: `concat_digits(12, 345)` is `12345`.
+pub fn concat_digits<T: PrimInt>(a: T, b: T) -> Option<T> {
+    a.checked_mul(&pow10(digit_count(b))?)?.checked_add(&b)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn normalizes_rationals() {
+        assert_eq!(r(4, -6), r(-2, 3));
+        assert_eq!(r(-2, 3).denominator(), 3);
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(r(6, 3).to_integer(), Some(2));
+        assert_eq!(r(1, 3).to_integer(), None);
+        assert_eq!(r(-1, 2).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn computes_with_rationals() {
+        assert_eq!(r(1, 2).checked_add(r(1, 3)), Some(r(5, 6)));
+        assert_eq!(r(1, 2).checked_sub(r(1, 3)), Some(r(1, 6)));
+        assert_eq!(r(2, 3).checked_mul(r(9, 4)), Some(r(3, 2)));
+        assert_eq!(r(2, 3).checked_div(r(4, 3)), Some(r(1, 2)));
+        assert_eq!(r(2, 3).checked_div(Rational::ZERO), None);
+        assert_eq!(Rational::from(i128::MAX).checked_add(1.into()), None);
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(1, i128::MAX));
+        assert!(r(i128::MAX - 1, i128::MAX) > r(i128::MAX - 2, i128::MAX - 1));
+        assert!(r(7, 5) > r(4, 3));
+        assert_eq!(r(5, 3).cmp(&r(10, 6)), Ordering::Equal);
+    }
+
+    #[test]
+    fn solves_two_by_two_systems() {
+        // the first claw machine from Day 13.
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Ok([80.into(), 40.into()])
+        );
+        assert_eq!(solve_2x2([[1, 1], [1, -1]], [1, 0]), Ok([r(1, 2), r(1, 2)]));
+        assert_eq!(
+            solve_2x2([[1, 2], [2, 4]], [3, 6]),
+            Err(SolveError::Singular)
+        );
+        assert_eq!(
+            solve_2x2([[i128::MAX, 2], [2, i128::MAX]], [1, 1]),
+            Err(SolveError::Overflow)
+        );
+    }
+
+    #[test]
+    fn solves_larger_systems() {
+        let a = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(
+            solve(&a, &[8, -11, -3]),
+            Ok(vec![2.into(), 3.into(), (-1).into()])
+        );
+        // needs a row swap, as the first pivot is zero.
+        assert_eq!(
+            solve(&[vec![0, 1], vec![1, 0]], &[5, 7]),
+            Ok(vec![7.into(), 5.into()])
+        );
+        assert_eq!(
+            solve(&[vec![1, 2], vec![2, 4]], &[1, 2]),
+            Err(SolveError::Singular)
+        );
+        assert_eq!(solve(&[vec![1, 2]], &[1]), Err(SolveError::Shape));
+    }
+
+    #[test]
+    fn computes_divisors_and_multiples() {
+        assert_eq!(gcd(12, 18), Some(6));
+        assert_eq!(gcd(-12, 18), Some(6));
+        assert_eq!(gcd(0u32, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(gcd(i64::MIN, -1), Some(1));
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_all(&[101u64, 103, 7]), Some(72821));
+        assert_eq!(lcm_all::<u64>(&[]), Some(1));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        let (g, x, y) = extended_gcd(240i64, 46).unwrap();
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+    }
+
+    #[test]
+    fn computes_modular_inverses() {
+        assert_eq!(mod_inverse(3i64, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i64, 9), None);
+        assert_eq!(mod_inverse(i64::MAX - 1, i64::MAX), Some(i64::MAX - 1));
+    }
+
+    #[test]
+    fn combines_congruences() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i64>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(0i64, i64::MAX), (1, i64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(digit_count(0u64), 1);
+        assert_eq!(digit_count(9u64), 1);
+        assert_eq!(digit_count(10u64), 2);
+        assert_eq!(digit_count(-123i32), 3);
+        assert_eq!(digit_count(u64::MAX), 20);
+        assert_eq!(digits(1203u32), vec![1, 2, 0, 3]);
+        assert_eq!(digits(0u32), vec![0]);
+        assert_eq!(split_digits(123456u64, 2), (1234, 56));
+        assert_eq!(split_digits(7u8, 3), (0, 7));
+        assert_eq!(concat_digits(12u64, 345), Some(12345));
+        assert_eq!(concat_digits(12u64, 0), Some(120));
+        assert_eq!(concat_digits(u64::MAX / 10, 99), None);
+    }
+}
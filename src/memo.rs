@@ -0,0 +1,214 @@
+/// Memoization for recursive functions.
+///
+/// A [`Memo`] caches results by key. The function being memoized receives the memo, so its
+/// recursive calls are cached as well:
+///
+/// ```ignore
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fib(memo, n - 1) + fib(memo, n - 2),
+///     })
+/// }
+/// ```
+///
+/// When the solution runs with `--verbose`, every memo prints its statistics once it is dropped.
+/// Verbose output is limited to the first run of a part, so a memo that is dropped after the part
+/// returned, e.g. one kept in a `static` or created outside the part, never prints.
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+
+use crate::template::runner;
+
+/// How often a [`Memo`] could answer from its cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Results that were forgotten because the memo was full.
+    pub evictions: u64,
+    /// Results that are currently cached.
+    pub entries: usize,
+}
+
+impl Stats {
+    /// The share of lookups that were cached, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )?;
+        if self.evictions > 0 {
+            write!(f, ", {} evictions", self.evictions)?;
+        }
+        Ok(())
+    }
+}
+
+/// A cache of the results of a function, by its arguments.
+pub struct Memo<K, V> {
+    name: &'static str,
+    cache: HashMap<K, V>,
+    /// The maximum number of cached results, if limited.
+    capacity: Option<usize>,
+    /// Cached keys in insertion order, to evict the oldest one when the memo is full.
+    order: VecDeque<K>,
+    stats: Stats,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            name: "memo",
+            cache: HashMap::new(),
+            capacity: None,
+            order: VecDeque::new(),
+            stats: Stats::default(),
+        }
+    }
+
+    /// Creates a memo that keeps at most `capacity` results, forgetting the oldest ones first.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut memo = Memo::new();
+        memo.capacity = Some(capacity);
+        memo
+    }
+
+    /// Sets the name the statistics are printed with in verbose mode.
+    pub fn named(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Returns the cached result for `key`, or computes and caches it. `compute` receives the memo
+    /// to make recursive calls with.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+
+        let value = compute(self);
+        if self.capacity == Some(0) {
+            return value;
+        }
+        if let Some(capacity) = self.capacity {
+            while self.cache.len() >= capacity {
+                let oldest = self.order.pop_front().expect("cached keys are ordered");
+                self.cache.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+            self.order.push_back(key.clone());
+        }
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            entries: self.cache.len(),
+            ..self.stats
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Prints the statistics if [`runner::is_verbose`] still holds, i.e. during a part's first run.
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if runner::is_verbose() {
+            let stats = Stats {
+                entries: self.cache.len(),
+                ..self.stats
+            };
+            eprintln!("{}: {stats}", self.name);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_compute(n, |memo| match n {
+            0 | 1 => n,
+            _ => fib(memo, n - 1) + fib(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+        let stats = memo.stats();
+        assert_eq!(stats.misses, 91);
+        assert_eq!(stats.hits, 88);
+        assert_eq!(stats.entries, 91);
+
+        assert_eq!(fib(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn limits_capacity() {
+        let mut memo = Memo::with_capacity(3);
+        assert_eq!(fib(&mut memo, 30), 832_040);
+        let stats = memo.stats();
+        assert_eq!(memo.len(), 3);
+        assert_eq!(stats.evictions, stats.misses - 3);
+
+        let mut memo = Memo::with_capacity(0);
+        assert_eq!(fib(&mut memo, 10), 55);
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats().hits, 0);
+    }
+
+    #[test]
+    fn displays_stats() {
+        let stats = Stats {
+            hits: 3,
+            misses: 1,
+            evictions: 0,
+            entries: 1,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 hits, 1 misses (75.0% hit rate), 1 entries"
+        );
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+    }
+}
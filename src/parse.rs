@@ -0,0 +1,237 @@
+/// Parser combinators for input shapes that come up in many puzzles, built on `nom`.
+///
+/// The combinators return plain `nom` parsers, so they compose with everything in `nom`.
+/// [`parse_all`] runs a parser on a whole input and turns failures into a [`ParseError`]
+/// pointing at the line and column where parsing stopped.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::{tag, take_till1},
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    error::ErrorKind,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+    Finish, IResult, Parser,
+};
+
+/// A failed parse, located in the original input.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    pub message: String,
+    /// The remainder of the line where parsing stopped.
+    pub found: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}, found ",
+            self.line, self.column, self.message
+        )?;
+        if self.found.is_empty() {
+            write!(f, "end of line.")
+        } else {
+            write!(f, "`{}`.", self.found)
+        }
+    }
+}
+
+impl ParseError {
+    fn new(input: &str, remaining: &str, kind: ErrorKind) -> Self {
+        let offset = input.len() - remaining.len();
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count()
+            + 1;
+
+        let message = match kind {
+            ErrorKind::Digit => "expected a number".into(),
+            ErrorKind::MapRes => "invalid value".into(),
+            ErrorKind::Tag | ErrorKind::Char | ErrorKind::OneOf => "unexpected text".into(),
+            ErrorKind::Space | ErrorKind::MultiSpace => "expected whitespace".into(),
+            ErrorKind::CrLf => "expected a line break".into(),
+            ErrorKind::Eof => "expected the end of the input".into(),
+            kind => format!("failed to parse ({})", kind.description()),
+        };
+
+        let found = remaining.lines().next().unwrap_or_default();
+        ParseError {
+            line,
+            column,
+            message,
+            found: found.chars().take(30).collect(),
+        }
+    }
+}
+
+/// Runs a parser on the complete input. Trailing whitespace, such as the final line break, is
+/// ignored; any other input left over is an error.
+///
+/// ```ignore
+/// let numbers: Vec<Vec<i64>> = parse_all(input, lines(list(signed)))?;
+/// ```
+pub fn parse_all<'a, O>(
+    input: &'a str,
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input)
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::new(input, e.input, e.code))
+}
+
+/// An integer with an optional sign, e.g. `-12` or `+3`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// An integer without a sign.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Items on one line, separated by spaces or tabs, e.g. `3   4`.
+pub fn list<'a, O>(
+    item: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// Items separated by commas, optionally followed by spaces, e.g. `75,47,61` or `1, 2`.
+pub fn comma_list<'a, O>(
+    item: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// One item per line.
+pub fn lines<'a, O>(
+    line: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, line)
+}
+
+/// Blocks of lines, separated by blank lines.
+pub fn blocks<'a, O>(
+    block: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_line, block)
+}
+
+/// The end of a line followed by an empty line, as found between blocks.
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    tuple((line_ending, space0, line_ending))
+        .map(|_| ())
+        .parse(input)
+}
+
+/// A `key: value` pair, e.g. `Register A: 729`. Returns the key without surrounding spaces.
+pub fn key_value<'a, O>(
+    value: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (&'a str, O)> {
+    separated_pair(
+        take_till1(|c| c == ':' || c == '\n').map(str::trim),
+        pair(char(':'), space0),
+        value,
+    )
+}
+
+/// A labelled pair of signed coordinates, e.g. `p=0,4` for the label `p`.
+pub fn coordinates<'a, T: FromStr>(
+    label: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T)> {
+    preceded(
+        pair(tag(label), delimited(space0, char('='), space0)),
+        separated_pair(signed, pair(char(','), space0), signed),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_integers() {
+        assert_eq!(signed::<i32>("-12 rest"), Ok((" rest", -12)));
+        assert_eq!(signed::<i32>("+3"), Ok(("", 3)));
+        assert_eq!(unsigned::<u8>("255"), Ok(("", 255)));
+        assert!(unsigned::<u8>("256").is_err());
+        assert!(signed::<u64>("-1").is_err());
+    }
+
+    #[test]
+    fn parses_lists() {
+        assert_eq!(
+            parse_all("3   4\n-1 2\n", lines(list(signed::<i32>))),
+            Ok(vec![vec![3, 4], vec![-1, 2]])
+        );
+        assert_eq!(
+            parse_all("75,47, 61", comma_list(unsigned::<u32>)),
+            Ok(vec![75, 47, 61])
+        );
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let input = "1|2\n3|4\n\n1,2\n";
+        let rule = separated_pair(unsigned::<u32>, char('|'), unsigned::<u32>);
+        let (rules, updates) = parse_all(
+            input,
+            separated_pair(lines(rule), blank_line, lines(comma_list(unsigned::<u32>))),
+        )
+        .unwrap();
+        assert_eq!(rules, vec![(1, 2), (3, 4)]);
+        assert_eq!(updates, vec![vec![1, 2]]);
+
+        assert_eq!(
+            parse_all("1\n2\n\n3", blocks(lines(unsigned::<u8>))),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn parses_key_values() {
+        assert_eq!(
+            parse_all("Register A: 729", key_value(unsigned::<u64>)),
+            Ok(("Register A", 729))
+        );
+        assert_eq!(
+            parse_all("Program: 0,1,5", key_value(comma_list(unsigned::<u8>))),
+            Ok(("Program", vec![0, 1, 5]))
+        );
+    }
+
+    #[test]
+    fn parses_coordinates() {
+        let robot = separated_pair(coordinates::<i32>("p"), space1, coordinates("v"));
+        assert_eq!(parse_all("p=0,4 v=3,-3", robot), Ok(((0, 4), (3, -3))));
+    }
+
+    #[test]
+    fn reports_error_locations() {
+        let error = parse_all("1 2\n3 x 4\n", lines(list(signed::<i32>))).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected the end of the input, found `x 4`."
+        );
+
+        let error = parse_all("Register A: -5", key_value(unsigned::<u64>)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 13));
+        assert_eq!(error.message, "expected a number");
+    }
+}
@@ -0,0 +1,193 @@
+/// Connected regions of equal cells in a [`Grid`], e.g. the plots of a garden.
+///
+/// Cells belong to the same region if they are equal and connected through their orthogonal
+/// neighbours. Regions are labelled with a flood fill in reading order of their first cell.
+use crate::grid::{Grid, Position, ORTHOGONAL};
+
+/// A connected region of equal cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region<T> {
+    /// The value all cells of the region share.
+    pub value: T,
+    /// The cells of the region, in the order they were flooded.
+    pub cells: Vec<Position>,
+    /// The number of cell edges that border another region or the edge of the grid.
+    pub perimeter: usize,
+    /// The number of straight sides of the fence around the region, including fences around
+    /// regions enclosed by this one.
+    pub sides: usize,
+}
+
+impl<T> Region<T> {
+    /// The number of cells.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// The regions of a grid, and which region each cell belongs to.
+#[derive(Clone, Debug)]
+pub struct Regions<T> {
+    labels: Grid<usize>,
+    regions: Vec<Region<T>>,
+}
+
+impl<T: Clone + PartialEq> Regions<T> {
+    /// Labels the regions of equal cells in `grid`.
+    pub fn label(grid: &Grid<T>) -> Self {
+        let mut labels: Grid<Option<usize>> = grid.map(|_| None);
+        let mut regions = vec![];
+
+        for start in grid.positions() {
+            if labels[start].is_some() {
+                continue;
+            }
+            let label = regions.len();
+            let value = &grid[start];
+            labels[start] = Some(label);
+            let mut cells = vec![];
+            let mut stack = vec![start];
+            while let Some(position) = stack.pop() {
+                cells.push(position);
+                for neighbour in grid.neighbours4(position) {
+                    if labels[neighbour].is_none() && grid[neighbour] == *value {
+                        labels[neighbour] = Some(label);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            regions.push(Region {
+                value: value.clone(),
+                cells,
+                perimeter: 0,
+                sides: 0,
+            });
+        }
+
+        let labels = labels.map(|label| label.expect("every cell is flooded"));
+        for region in &mut regions {
+            region.perimeter = region
+                .cells
+                .iter()
+                .map(|&position| edges(&labels, position))
+                .sum();
+            region.sides = region
+                .cells
+                .iter()
+                .map(|&position| corners(&labels, position))
+                .sum();
+        }
+        Regions { labels, regions }
+    }
+}
+
+impl<T> Regions<T> {
+    /// The number of regions.
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// The label of each cell, which is the index of its region.
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+
+    /// The region a cell belongs to.
+    pub fn region_at(&self, position: Position) -> &Region<T> {
+        &self.regions[self.labels[position]]
+    }
+
+    /// The regions, ordered by their label.
+    pub fn iter(&self) -> std::slice::Iter<'_, Region<T>> {
+        self.regions.iter()
+    }
+}
+
+/// Whether the cell at `offset` from `position` lies within the grid and has the same label.
+fn same_label(labels: &Grid<usize>, position: Position, offset: (isize, isize)) -> bool {
+    labels
+        .offset(position, offset)
+        .is_some_and(|neighbour| labels[neighbour] == labels[position])
+}
+
+/// The number of edges of a cell that border another region.
+fn edges(labels: &Grid<usize>, position: Position) -> usize {
+    ORTHOGONAL
+        .into_iter()
+        .filter(|&offset| !same_label(labels, position, offset))
+        .count()
+}
+
+/// The number of corners of the region's fence at a cell. A polygon has as many sides as corners.
+fn corners(labels: &Grid<usize>, position: Position) -> usize {
+    (0..4)
+        .filter(|&i| {
+            let (a, b) = (ORTHOGONAL[i], ORTHOGONAL[(i + 1) % 4]);
+            let (same_a, same_b) = (
+                same_label(labels, position, a),
+                same_label(labels, position, b),
+            );
+            // an outer corner, or an inner corner where the diagonal cell belongs to another region.
+            (!same_a && !same_b)
+                || (same_a && same_b && !same_label(labels, position, (a.0 + b.0, a.1 + b.1)))
+        })
+        .count()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn regions(input: &str) -> Regions<char> {
+        Regions::label(&input.parse().unwrap())
+    }
+
+    fn prices(regions: &Regions<char>) -> (usize, usize) {
+        regions.iter().fold((0, 0), |(by_perimeter, by_sides), r| {
+            (
+                by_perimeter + r.area() * r.perimeter,
+                by_sides + r.area() * r.sides,
+            )
+        })
+    }
+
+    #[test]
+    fn labels_regions() {
+        let regions = regions("AAAA\nBBCD\nBBCC\nEEEC\n");
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            regions.labels().to_string(),
+            "0000\n1123\n1122\n4442".to_string()
+        );
+
+        let c = regions.region_at((1, 2));
+        assert_eq!((c.value, c.area(), c.perimeter, c.sides), ('C', 4, 10, 8));
+        assert_eq!(prices(&regions), (140, 80));
+    }
+
+    #[test]
+    fn separates_equal_regions_that_do_not_touch() {
+        let regions = regions("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n");
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.iter().filter(|r| r.value == 'X').count(), 4);
+        assert_eq!(prices(&regions), (772, 436));
+    }
+
+    #[test]
+    fn counts_sides_of_enclosed_regions() {
+        assert_eq!(
+            prices(&regions("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE\n")).1,
+            236
+        );
+        assert_eq!(
+            prices(&regions("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n")).1,
+            368
+        );
+    }
+}
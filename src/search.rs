@@ -0,0 +1,522 @@
+/// Graph searches over implicit graphs.
+///
+/// Graphs are described by a start node and a successor function, so any `Clone + Eq + Hash`
+/// value works as a node: grid positions, `(Point, Direction)` states, strings, ...
+///
+/// ```ignore
+/// let path = bfs(start, |&p| grid.neighbours4(p).filter(|&n| grid[n] != '#'), |&p| p == goal);
+/// ```
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use num_traits::Zero;
+
+/// Marks the start node, which has no parent.
+const NO_PARENT: usize = usize::MAX;
+
+/// Follows parent links from `index` back to the start.
+fn build_path<N: Clone>(nodes: &[(N, usize)], mut index: usize) -> Vec<N> {
+    let mut path = vec![];
+    while index != NO_PARENT {
+        path.push(nodes[index].0.clone());
+        index = nodes[index].1;
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search. Returns a path from `start` to the closest goal with the fewest steps,
+/// including both ends.
+pub fn bfs<N, IN>(
+    start: N,
+    mut successors: impl FnMut(&N) -> IN,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    IN: IntoIterator<Item = N>,
+{
+    let mut nodes = vec![(start.clone(), NO_PARENT)];
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&nodes[index].0) {
+            return Some(build_path(&nodes, index));
+        }
+        for next in successors(&nodes[index].0) {
+            if visited.insert(next.clone()) {
+                queue.push_back(nodes.len());
+                nodes.push((next, index));
+            }
+        }
+    }
+    None
+}
+
+/// The number of steps from `start` to every reachable node.
+pub fn bfs_distances<N, IN>(start: N, mut successors: impl FnMut(&N) -> IN) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    IN: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Depth-first search. Returns the first path found from `start` to a goal, which is not
+/// necessarily the shortest.
+pub fn dfs<N, IN>(
+    start: N,
+    mut successors: impl FnMut(&N) -> IN,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    IN: IntoIterator<Item = N>,
+{
+    let mut nodes = vec![(start, NO_PARENT)];
+    let mut visited = HashSet::new();
+    let mut stack = vec![0];
+
+    while let Some(index) = stack.pop() {
+        let node = nodes[index].0.clone();
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(build_path(&nodes, index));
+        }
+
+        // push in reverse, so successors are visited in the order they are returned.
+        let first = stack.len();
+        for next in successors(&node) {
+            if !visited.contains(&next) {
+                stack.push(nodes.len());
+                nodes.push((next, index));
+            }
+        }
+        stack[first..].reverse();
+    }
+    None
+}
+
+/// All nodes reachable from `start`, including it, in depth-first order.
+pub fn reachable<N, IN>(start: N, mut successors: impl FnMut(&N) -> IN) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    IN: IntoIterator<Item = N>,
+{
+    let mut order = vec![];
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let first = stack.len();
+        stack.extend(
+            successors(&node)
+                .into_iter()
+                .filter(|n| !visited.contains(n)),
+        );
+        stack[first..].reverse();
+        order.push(node);
+    }
+    order
+}
+
+/// An entry in the priority queue of Dijkstra and A*, ordered so the lowest estimate is popped
+/// first.
+struct Candidate<C> {
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // on equal estimates, prefer the candidate that is further along.
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> PartialEq for Candidate<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Candidate<C> {}
+
+/// Dijkstra's algorithm for non-negative edge costs. Returns a cheapest path from `start` to a
+/// goal, including both ends, and its cost.
+pub fn dijkstra<N, C, IN>(
+    start: N,
+    successors: impl FnMut(&N) -> IN,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search. The heuristic estimates the remaining cost to a goal and must never overestimate
+/// it, otherwise the returned path may not be the cheapest.
+pub fn astar<N, C, IN>(
+    start: N,
+    mut successors: impl FnMut(&N) -> IN,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: heuristic(&start),
+        cost: C::zero(),
+        index: 0,
+    }]);
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![(start, NO_PARENT)];
+    let mut costs = vec![C::zero()];
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > costs[index] {
+            // a cheaper way to this node was found after this candidate was queued.
+            continue;
+        }
+        let node = nodes[index].0.clone();
+        if is_goal(&node) {
+            return Some((build_path(&nodes, index), cost));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let next_index = match indices.entry(next) {
+                Entry::Vacant(entry) => {
+                    nodes.push((entry.key().clone(), index));
+                    costs.push(next_cost);
+                    *entry.insert(nodes.len() - 1)
+                }
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    if costs[next_index] <= next_cost {
+                        continue;
+                    }
+                    nodes[next_index].1 = index;
+                    costs[next_index] = next_cost;
+                    next_index
+                }
+            };
+            heap.push(Candidate {
+                estimate: next_cost + heuristic(&nodes[next_index].0),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+    None
+}
+
+/// The cheapest costs from a start node to every reachable node, together with all
+/// predecessors on cheapest paths. See [`shortest_paths`].
+pub struct ShortestPaths<N, C> {
+    costs: HashMap<N, C>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+/// Runs Dijkstra's algorithm over the whole graph, tracking every cheapest path instead of
+/// just one. Useful for questions like "which tiles are on any best path?".
+pub fn shortest_paths<N, C, IN>(
+    start: N,
+    mut successors: impl FnMut(&N) -> IN,
+) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: C::zero(),
+        cost: C::zero(),
+        index: 0,
+    }]);
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut nodes = vec![start];
+    let mut costs = vec![C::zero()];
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]];
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+        let node = nodes[index].clone();
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let next_index = match indices.entry(next) {
+                Entry::Vacant(entry) => {
+                    nodes.push(entry.key().clone());
+                    costs.push(next_cost);
+                    predecessors.push(vec![index]);
+                    *entry.insert(nodes.len() - 1)
+                }
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    match next_cost.cmp(&costs[next_index]) {
+                        Ordering::Greater => continue,
+                        Ordering::Equal => {
+                            if !predecessors[next_index].contains(&index) {
+                                predecessors[next_index].push(index);
+                            }
+                            continue;
+                        }
+                        Ordering::Less => {
+                            costs[next_index] = next_cost;
+                            predecessors[next_index] = vec![index];
+                            next_index
+                        }
+                    }
+                }
+            };
+            heap.push(Candidate {
+                estimate: next_cost,
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+
+    let predecessors = predecessors
+        .into_iter()
+        .enumerate()
+        .map(|(i, previous)| {
+            let previous = previous.into_iter().map(|p| nodes[p].clone()).collect();
+            (nodes[i].clone(), previous)
+        })
+        .collect();
+    ShortestPaths {
+        costs: nodes.into_iter().zip(costs).collect(),
+        predecessors,
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> ShortestPaths<N, C> {
+    /// The cost of the cheapest path to a node, or `None` if it is unreachable.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The nodes preceding a node on its cheapest paths.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One of the cheapest paths to a node, including the start and the node.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.costs.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All nodes that lie on any cheapest path to the given nodes, including them.
+    pub fn nodes_on_paths<'a>(&self, targets: impl IntoIterator<Item = &'a N>) -> HashSet<N>
+    where
+        N: 'a,
+    {
+        let mut nodes = HashSet::new();
+        let mut stack: Vec<N> = targets
+            .into_iter()
+            .filter(|target| self.costs.contains_key(target))
+            .cloned()
+            .collect();
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+        nodes
+    }
+
+    /// The number of distinct cheapest paths to a node.
+    pub fn count_paths(&self, node: &N) -> u64 {
+        count_paths(
+            node.clone(),
+            |n| self.predecessors(n).to_vec(),
+            |n| self.predecessors(n).is_empty() && self.costs.contains_key(n),
+        )
+    }
+}
+
+/// The number of distinct paths from `start` to any goal. The graph must not contain cycles;
+/// paths end at the first goal they reach.
+pub fn count_paths<N, IN>(
+    start: N,
+    mut successors: impl FnMut(&N) -> IN,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Clone + Eq + Hash,
+    IN: IntoIterator<Item = N>,
+{
+    fn count<N: Clone + Eq + Hash, IN: IntoIterator<Item = N>>(
+        node: N,
+        successors: &mut impl FnMut(&N) -> IN,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        counts: &mut HashMap<N, u64>,
+    ) -> u64 {
+        if let Some(&paths) = counts.get(&node) {
+            return paths;
+        }
+        let paths = if is_goal(&node) {
+            1
+        } else {
+            let mut paths = 0;
+            for next in successors(&node) {
+                paths += count(next, successors, is_goal, counts);
+            }
+            paths
+        };
+        counts.insert(node, paths);
+        paths
+    }
+
+    count(start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.position_of(&'S').unwrap();
+        let end = grid.position_of(&'E').unwrap();
+        (grid, start, end)
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let (grid, start, end) = maze();
+        let open = |&p: &(usize, usize)| grid.neighbours4(p).filter(|&n| grid[n] != '#');
+
+        let path = bfs(start, open, |&p| p == end).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert_eq!(path.len() - 1, 15);
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|n| n == w[1])));
+
+        assert_eq!(bfs_distances(start, open).get(&end), Some(&15));
+        assert_eq!(bfs(start, open, |&p| p == (0, 3)), None);
+    }
+
+    #[test]
+    fn finds_some_path_with_dfs() {
+        let (grid, start, end) = maze();
+        let open = |&p: &(usize, usize)| grid.neighbours4(p).filter(|&n| grid[n] != '#');
+
+        let path = dfs(start, open, |&p| p == end).unwrap();
+        assert_eq!((path.first(), path.last()), (Some(&start), Some(&end)));
+        assert_eq!(reachable(start, open).len(), 27);
+        assert_eq!(reachable(start, open)[0], start);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // a graph where the direct edge is more expensive than the detour.
+        let edges = |&n: &u32| match n {
+            0 => vec![(1, 7), (2, 1)],
+            2 => vec![(3, 2)],
+            3 => vec![(1, 1)],
+            _ => vec![],
+        };
+        assert_eq!(dijkstra(0, edges, |&n| n == 1), Some((vec![0, 2, 3, 1], 4)));
+        assert_eq!(dijkstra(0, edges, |&n| n == 9), None);
+
+        let (grid, start, end) = maze();
+        let steps = |&p: &(usize, usize)| {
+            grid.neighbours4(p)
+                .filter(|&n| grid[n] != '#')
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+        let manhattan = |&(r, c): &(usize, usize)| r.abs_diff(end.0) + c.abs_diff(end.1);
+        let (path, cost) = astar(start, steps, manhattan, |&p| p == end).unwrap();
+        assert_eq!((path.len() - 1, cost), (15, 15));
+    }
+
+    #[test]
+    fn tracks_all_shortest_paths() {
+        // two equally cheap ways from 0 to 3, and a more expensive one.
+        let edges = |&n: &u32| match n {
+            0 => vec![(1, 1), (2, 1), (3, 5)],
+            1 | 2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let paths = shortest_paths(0, edges);
+        assert_eq!(paths.cost(&3), Some(2));
+        assert_eq!(paths.cost(&4), None);
+        assert_eq!(paths.count_paths(&3), 2);
+        assert_eq!(paths.count_paths(&0), 1);
+        assert_eq!(paths.nodes_on_paths([&3]).len(), 4);
+        let path = paths.path(&3).unwrap();
+        assert_eq!((path.len(), path[0], path[2]), (3, 0, 3));
+    }
+
+    #[test]
+    fn counts_paths_in_dags() {
+        // every path through a 3x3 lattice moving only right or down.
+        let moves = |&(r, c): &(u32, u32)| {
+            [(r + 1, c), (r, c + 1)]
+                .into_iter()
+                .filter(|&(r, c)| r < 3 && c < 3)
+        };
+        assert_eq!(count_paths((0, 0), moves, |&p| p == (2, 2)), 6);
+        assert_eq!(count_paths((0, 0), moves, |&(r, _)| r == 5), 0);
+    }
+}
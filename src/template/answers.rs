@@ -0,0 +1,260 @@
+/// Answers of solutions, recorded by `cargo time --store`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{config::Config, Day};
+
+/// Represents the recorded answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl DayAnswers {
+    /// Builds the answers of a day from the answers parsed from a solution's output.
+    /// Parts that were not solved or failed are not recorded.
+    pub fn from_parsed(day: Day, parsed: &BTreeMap<u8, String>) -> Self {
+        let answer = |part: u8| {
+            parsed
+                .get(&part)
+                .filter(|answer| !answer.is_empty() && !answer.starts_with('✖'))
+                .cloned()
+        };
+
+        DayAnswers {
+            day,
+            part_1: answer(1),
+            part_2: answer(2),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part_1.is_none() && self.part_2.is_none()
+    }
+}
+
+/// Represents the recorded answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(&Config::get().answers_path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(&Config::get().answers_path) {
+            Err(e) if e.kind() == ErrorKind::NotFound => return Answers::default(),
+            s => s.map_err(|x| x.to_string()).and_then(Answers::try_from),
+        };
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Merge two sets of answers, overwriting `self` with `other` if present.
+    /// Parts missing from `other` keep their previously recorded answer.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data: Vec<DayAnswers> = self.data.clone();
+
+        for answers in &new.data {
+            match data.iter_mut().find(|a| a.day == answers.day) {
+                Some(existing) => {
+                    existing.part_1 = answers.part_1.clone().or(existing.part_1.take());
+                    existing.part_2 = answers.part_2.clone().or(existing.part_2.take());
+                }
+                None => data.push(answers.clone()),
+            }
+        }
+
+        data.sort_unstable_by_key(|a| a.day);
+        Answers { data }
+    }
+
+    /// The recorded answer of a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answers = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answers.part_1.as_deref(),
+            2 => answers.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_2 to be null or string.")?;
+
+        Ok(DayAnswers {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::day;
+
+    use super::{Answers, DayAnswers};
+
+    #[test]
+    fn round_trips_through_json() {
+        let answers = Answers {
+            data: vec![DayAnswers {
+                day: day!(1),
+                part_1: Some("11".into()),
+                part_2: None,
+            }],
+        };
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+        assert_eq!(parsed.get(day!(1), 1), Some("11"));
+        assert_eq!(parsed.get(day!(1), 2), None);
+    }
+
+    #[test]
+    fn merges_parts() {
+        let old = Answers {
+            data: vec![DayAnswers {
+                day: day!(2),
+                part_1: Some("1".into()),
+                part_2: Some("2".into()),
+            }],
+        };
+        let new = Answers {
+            data: vec![
+                DayAnswers {
+                    day: day!(2),
+                    part_1: Some("3".into()),
+                    part_2: None,
+                },
+                DayAnswers {
+                    day: day!(1),
+                    part_1: Some("4".into()),
+                    part_2: None,
+                },
+            ],
+        };
+        let merged = old.merge(&new);
+        assert_eq!(merged.data[0].day, day!(1));
+        assert_eq!(merged.get(day!(2), 1), Some("3"));
+        assert_eq!(merged.get(day!(2), 2), Some("2"));
+    }
+
+    #[test]
+    fn skips_unsolved_parts() {
+        let parsed = BTreeMap::from([(1, "42".to_string()), (2, "✖ Error: oops".to_string())]);
+        let answers = DayAnswers::from_parsed(day!(1), &parsed);
+        assert_eq!(answers.part_1, Some("42".into()));
+        assert_eq!(answers.part_2, None);
+    }
+}
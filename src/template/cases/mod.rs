@@ -0,0 +1,151 @@
+/// Named example inputs with expected answers, read from `data/examples/NN.cases`.
+///
+/// A cases file consists of a free-form description followed by any number of cases.
+/// Each case starts with a `=== <name>` line, followed by its input and the expected answers:
+///
+/// ```text
+/// Edge cases for day 2.
+///
+/// === single_level
+/// 5
+/// --- part 1: 1
+/// --- part 2: 1
+/// ```
+///
+/// Case names have to be valid rust identifiers, as [`example_cases!`](crate::example_cases) turns them into tests.
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::template::config::Config;
+use crate::template::runner::{Answer, PartResult};
+use crate::template::{read_path, Day, InputError};
+
+pub use syntax::Case;
+use syntax::SyntaxError;
+
+mod syntax;
+
+/// An error which can be returned when reading a cases file.
+#[derive(Debug)]
+pub enum CasesError {
+    Input(InputError),
+    Syntax { line: usize, message: String },
+}
+
+impl Error for CasesError {}
+
+impl Display for CasesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CasesError::Input(e) => write!(f, "{e}"),
+            CasesError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl From<SyntaxError> for CasesError {
+    fn from(e: SyntaxError) -> Self {
+        CasesError::Syntax {
+            line: e.line,
+            message: e.message,
+        }
+    }
+}
+
+impl From<InputError> for CasesError {
+    fn from(e: InputError) -> Self {
+        CasesError::Input(e)
+    }
+}
+
+/// Reads all cases of a day from its cases file.
+pub fn read_cases(day: Day) -> Result<Vec<Case>, CasesError> {
+    let path = std::env::current_dir()
+        .unwrap()
+        .join(Config::get().day_path("examples", day, "cases"));
+    parse_cases(&read_path(&path)?)
+}
+
+/// Parses the contents of a cases file.
+pub fn parse_cases(s: &str) -> Result<Vec<Case>, CasesError> {
+    Ok(syntax::parse(s)?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Loads a single case of a day. Panics with a descriptive message if it can not be read.
+pub fn load_case(day: Day, name: &str) -> Case {
+    let cases = read_cases(day).unwrap_or_else(|e| panic!("could not read cases: {e}"));
+
+    cases
+        .into_iter()
+        .find(|case| case.name == name)
+        .unwrap_or_else(|| panic!("could not find case `{name}` for day {day}."))
+}
+
+/// Checks the answer of a part against the answer expected by a case.
+/// Parts without an expected answer are skipped.
+#[track_caller]
+pub fn check_part<R: PartResult>(case: &Case, part: u8, func: impl Fn(&str) -> R) {
+    let Some(expected) = case.answers.get(&part) else {
+        return;
+    };
+
+    assert_answer(
+        func(&case.input),
+        expected,
+        &format!("part {part} of case `{}`", case.name),
+    );
+}
+
+/// Asserts that a part returned the expected answer, comparing its `Display` output.
+#[track_caller]
+pub(crate) fn assert_answer<R: PartResult>(result: R, expected: &str, description: &str) {
+    match result.into_answer() {
+        Answer::Solved(result) => assert_eq!(
+            result.to_string().trim_end(),
+            expected.trim_end(),
+            "wrong answer for {description}."
+        ),
+        Answer::Unsolved => panic!("{description} returned no answer, expected {expected}."),
+        Answer::Failed(e) => panic!("{description} failed: {e}, expected {expected}."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cases, CasesError};
+
+    #[test]
+    fn parses_cases() {
+        let cases = parse_cases(
+            "Some description.\n\n=== first\n1 2\n3 4\n\n--- part 1: 10\n--- part 2: 20\n\n=== second\n#.#\n--- part 2: x\n",
+        )
+        .unwrap();
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].input, "1 2\n3 4\n");
+        assert_eq!(cases[0].answers.get(&1).unwrap(), "10");
+        assert_eq!(cases[0].answers.get(&2).unwrap(), "20");
+        assert_eq!(cases[1].input, "#.#\n");
+        assert_eq!(cases[1].answers.get(&1), None);
+        assert_eq!(cases[1].answers.get(&2).unwrap(), "x");
+    }
+
+    #[test]
+    fn rejects_invalid_cases() {
+        let line = |s: &str| match parse_cases(s) {
+            Err(CasesError::Syntax { line, .. }) => line,
+            _ => panic!("expected syntax error for {s:?}"),
+        };
+
+        assert_eq!(line("=== not an ident\n1\n--- part 1: 1"), 1);
+        assert_eq!(line("=== a\n1\n--- part 1: 1\n=== a\n2\n--- part 1: 2"), 4);
+        assert_eq!(line("=== a\n1\n--- part one: 1"), 3);
+        assert_eq!(line("=== a\n1\n--- part 1: 1\n2"), 4);
+        assert_eq!(line("=== a\n1\n"), 2);
+    }
+}
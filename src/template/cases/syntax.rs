@@ -0,0 +1,124 @@
+/// The format of cases files, see [`super`].
+///
+/// Only depends on `std`, so `build.rs` can include it to generate the tests of `example_cases!`.
+use std::collections::BTreeMap;
+
+const CASE_PREFIX: &str = "=== ";
+const ANSWER_PREFIX: &str = "--- part ";
+
+/// A named example input with its expected answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: String,
+    /// Expected answer for each part listed in the case.
+    pub answers: BTreeMap<u8, String>,
+}
+
+/// A malformed line in a cases file.
+#[derive(Debug)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parses the contents of a cases file.
+pub fn parse(s: &str) -> Result<Vec<Case>, SyntaxError> {
+    let mut cases: Vec<Case> = vec![];
+    let mut input: Vec<&str> = vec![];
+    let mut in_answers = false;
+
+    let finish_input = |case: &mut Case, input: &mut Vec<&str>| {
+        while input.last().is_some_and(|l| l.trim().is_empty()) {
+            input.pop();
+        }
+        case.input = input.iter().map(|l| format!("{l}\n")).collect();
+        input.clear();
+    };
+
+    for (index, line) in s.lines().enumerate() {
+        let error = |message: String| SyntaxError {
+            line: index + 1,
+            message,
+        };
+
+        if let Some(name) = line.strip_prefix(CASE_PREFIX) {
+            if let Some(case) = cases.last_mut() {
+                if !in_answers {
+                    finish_input(case, &mut input);
+                }
+            }
+
+            let name = name.trim();
+            if !is_identifier(name) {
+                return Err(error(format!(
+                    "case name `{name}` is not a valid identifier."
+                )));
+            }
+            if cases.iter().any(|c| c.name == name) {
+                return Err(error(format!("duplicate case `{name}`.")));
+            }
+
+            cases.push(Case {
+                name: name.into(),
+                input: String::new(),
+                answers: BTreeMap::new(),
+            });
+            in_answers = false;
+            continue;
+        }
+
+        // lines before the first case describe the file.
+        let Some(case) = cases.last_mut() else {
+            continue;
+        };
+
+        if let Some(answer) = line.strip_prefix(ANSWER_PREFIX) {
+            if !in_answers {
+                finish_input(case, &mut input);
+                in_answers = true;
+            }
+
+            let (part, answer) = answer
+                .split_once(':')
+                .and_then(|(part, answer)| Some((part.trim().parse::<u8>().ok()?, answer.trim())))
+                .ok_or_else(|| error("expected `--- part <n>: <answer>`.".into()))?;
+
+            if case.answers.insert(part, answer.into()).is_some() {
+                return Err(error(format!("duplicate answer for part {part}.")));
+            }
+        } else if in_answers {
+            if !line.trim().is_empty() {
+                return Err(error(format!(
+                    "unexpected input after the answers of case `{}`.",
+                    case.name
+                )));
+            }
+        } else {
+            input.push(line);
+        }
+    }
+
+    if let Some(case) = cases.last_mut() {
+        if !in_answers {
+            finish_input(case, &mut input);
+        }
+    }
+
+    if let Some(case) = cases.iter().find(|c| c.answers.is_empty()) {
+        return Err(SyntaxError {
+            line: s.lines().count(),
+            message: format!("case `{}` has no expected answers.", case.name),
+        });
+    }
+
+    Ok(cases)
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
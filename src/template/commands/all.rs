@@ -1,5 +1,6 @@
+use crate::template::commands::CommandError;
 use crate::template::{all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool) -> Result<(), CommandError> {
+    run_multi(&all_days().collect(), is_release, false).2
 }
@@ -0,0 +1,73 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::PathBuf,
+};
+
+use crate::template::commands::CommandError;
+use crate::template::config::Config;
+use crate::template::generators::{self, Generator};
+use crate::template::Day;
+
+pub fn handle(
+    day: Option<Day>,
+    size: Option<usize>,
+    seed: u64,
+    out: Option<PathBuf>,
+    overwrite: bool,
+) -> Result<(), CommandError> {
+    let generators: Vec<Generator> = match day {
+        Some(day) => vec![generators::generator(day).ok_or_else(|| {
+            CommandError::Usage(format!("there is no input generator for day {day}."))
+        })?],
+        None => generators::all_generators().collect(),
+    };
+
+    if let Some(out) = &out {
+        fs::create_dir_all(out)
+            .map_err(|e| CommandError::IO("Failed to create output directory".into(), e))?;
+    }
+
+    for generator in generators {
+        let path = match &out {
+            Some(out) => out.join(format!("{}.txt", generator.day)),
+            None => Config::get().input_path(generator.day),
+        };
+        let input = generator.generate(size.unwrap_or(generator.default_size), seed);
+
+        // `cargo scaffold` leaves an empty placeholder for the puzzle input.
+        let is_placeholder = fs::metadata(&path).is_ok_and(|metadata| metadata.len() == 0);
+        let mut options = OpenOptions::new();
+        if overwrite || is_placeholder {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+
+        let mut file = match options.write(true).open(&path) {
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                let message = format!(
+                    "\"{}\" already exists, pass `--overwrite` to replace it.",
+                    path.display()
+                );
+                // when generating every day, one existing input should not stop the others.
+                if day.is_some() {
+                    return Err(CommandError::Usage(message));
+                }
+                eprintln!("Skipping day {}: {message}", generator.day);
+                continue;
+            }
+            file => file.map_err(|e| CommandError::IO("Failed to create input file".into(), e))?,
+        };
+
+        file.write_all(input.as_bytes())
+            .map_err(|e| CommandError::IO("Failed to write input file".into(), e))?;
+        println!(
+            "Generated input for day {} at \"{}\"",
+            generator.day,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,153 @@
+use std::{fmt::Display, io, process::ExitStatus};
+
+use crate::template::{aoc_cli::AocCommandError, Day};
+
 pub mod all;
 pub mod download;
+pub mod generate;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod tui;
+pub mod watch;
+
+/// Exit codes of the main binary and the solution binaries.
+pub mod exit_codes {
+    /// The command was called with invalid arguments.
+    pub const USAGE: i32 = 1;
+    /// A solution part returned an error.
+    pub const SOLUTION_FAILED: i32 = 2;
+    /// A solution could not read its input.
+    pub const INPUT_UNREADABLE: i32 = 3;
+    /// A solution could not be built, or crashed.
+    pub const CARGO_FAILED: i32 = 4;
+    /// aoc-cli is not installed.
+    pub const AOC_CLI_MISSING: i32 = 5;
+    /// aoc-cli exited with a non-zero status.
+    pub const AOC_CLI_FAILED: i32 = 6;
+    /// A file could not be read or written.
+    pub const IO: i32 = 7;
+}
+
+/// An error which can be returned by a command handler.
+#[derive(Debug)]
+pub enum CommandError {
+    Usage(String),
+    SolutionFailed(Day),
+    InputUnreadable(Day),
+    Cargo(String),
+    Crashed(Day, String),
+    AocCli(AocCommandError),
+    /// A solution binary failed to submit its answer via aoc-cli.
+    AocCliFailed(Day),
+    IO(String, io::Error),
+}
+
+impl CommandError {
+    /// Maps the error to the exit code documented in the readme.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CommandError::Usage(_) => exit_codes::USAGE,
+            CommandError::SolutionFailed(_) => exit_codes::SOLUTION_FAILED,
+            CommandError::InputUnreadable(_) => exit_codes::INPUT_UNREADABLE,
+            CommandError::Cargo(_) | CommandError::Crashed(_, _) => exit_codes::CARGO_FAILED,
+            CommandError::AocCli(
+                AocCommandError::CommandNotFound | AocCommandError::CommandNotCallable,
+            ) => exit_codes::AOC_CLI_MISSING,
+            CommandError::AocCli(AocCommandError::BadExitStatus(_))
+            | CommandError::AocCliFailed(_) => exit_codes::AOC_CLI_FAILED,
+            CommandError::IO(_, _) => exit_codes::IO,
+        }
+    }
+
+    /// Interprets the exit status of a `cargo run` invocation of a solution binary.
+    /// Exit codes of the solution binary are passed through, anything else means cargo failed.
+    pub fn from_solution_status(day: Day, status: ExitStatus) -> Result<(), Self> {
+        match status.code() {
+            Some(0) => Ok(()),
+            Some(exit_codes::USAGE) => Err(CommandError::Usage(format!(
+                "solution for day {day} was called with invalid arguments."
+            ))),
+            Some(exit_codes::SOLUTION_FAILED) => Err(CommandError::SolutionFailed(day)),
+            Some(exit_codes::INPUT_UNREADABLE) => Err(CommandError::InputUnreadable(day)),
+            Some(exit_codes::AOC_CLI_MISSING) => {
+                Err(CommandError::AocCli(AocCommandError::CommandNotFound))
+            }
+            Some(exit_codes::AOC_CLI_FAILED) => Err(CommandError::AocCliFailed(day)),
+            _ => Err(CommandError::Cargo(format!(
+                "solution for day {day} exited with {status}."
+            ))),
+        }
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Usage(e) => write!(f, "{e}"),
+            CommandError::SolutionFailed(day) => write!(f, "solution for day {day} failed."),
+            CommandError::InputUnreadable(day) => {
+                write!(f, "solution for day {day} could not read its input.")
+            }
+            CommandError::Cargo(e) => write!(f, "{e}"),
+            CommandError::Crashed(day, reason) => {
+                write!(f, "solution for day {day} crashed: {reason}")
+            }
+            CommandError::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            CommandError::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            CommandError::AocCliFailed(day) => {
+                write!(f, "solution for day {day} could not submit its answer via aoc-cli.")
+            }
+            CommandError::IO(context, e) => write!(f, "{context}: {e}"),
+        }
+    }
+}
+
+impl From<AocCommandError> for CommandError {
+    fn from(e: AocCommandError) -> Self {
+        CommandError::AocCli(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "test_lib", unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use super::{exit_codes, CommandError};
+    use crate::day;
+
+    /// The exit code `cargo solve` reports for a solution binary that exited with `code`.
+    fn reported_code(code: i32) -> i32 {
+        CommandError::from_solution_status(day!(1), ExitStatus::from_raw(code << 8))
+            .err()
+            .map_or(0, |e| e.exit_code())
+    }
+
+    #[test]
+    fn passes_solution_exit_codes_through() {
+        assert_eq!(reported_code(0), 0);
+        for code in [
+            exit_codes::USAGE,
+            exit_codes::SOLUTION_FAILED,
+            exit_codes::INPUT_UNREADABLE,
+            exit_codes::AOC_CLI_MISSING,
+            exit_codes::AOC_CLI_FAILED,
+        ] {
+            assert_eq!(reported_code(code), code);
+        }
+    }
+
+    #[test]
+    fn reports_other_exit_codes_as_cargo_failures() {
+        assert_eq!(reported_code(101), exit_codes::CARGO_FAILED);
+        assert_eq!(reported_code(exit_codes::IO), exit_codes::CARGO_FAILED);
+    }
+}
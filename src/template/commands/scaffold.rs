@@ -1,15 +1,16 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::{config::Config, Day};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +20,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +28,38 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+pub fn handle(day: Day, overwrite: bool) -> Result<(), CommandError> {
+    let config = Config::get();
+    let input_path = config.input_path(day);
+    let example_path = config.example_path(day);
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    let template = match &config.template_path {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| CommandError::IO("Failed to read module template".into(), e))?,
+        None => MODULE_TEMPLATE.to_string(),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
+    let mut file = safe_create_file(Path::new(&module_path), overwrite)
+        .map_err(|e| CommandError::IO("Failed to create module file".into(), e))?;
+
+    file.write_all(
+        template
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
-    }
+    )
+    .map_err(|e| CommandError::IO("Failed to write module contents".into(), e))?;
+    println!("Created module file \"{}\"", &module_path);
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&input_path)
+        .map_err(|e| CommandError::IO("Failed to create input file".into(), e))?;
+    println!("Created empty input file \"{}\"", input_path.display());
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
-    }
+    create_file(&example_path)
+        .map_err(|e| CommandError::IO("Failed to create example file".into(), e))?;
+    println!("Created empty example file \"{}\"", example_path.display());
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
@@ -1,8 +1,20 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::commands::CommandError;
+use crate::template::visualize::Settings;
+use crate::template::{Day, InputSource};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: &InputSource,
+    compare: bool,
+    verbose: bool,
+    visualize: Option<&Settings>,
+) -> Result<(), CommandError> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,18 +29,31 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.extend(input.to_args());
+
+    if compare {
+        cmd_args.push("--compare".to_string());
+    }
+
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
+    if let Some(visualize) = visualize {
+        cmd_args.extend(visualize.to_args());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| CommandError::Cargo(format!("failed to call cargo: {e}")))?;
 
-    cmd.wait().unwrap();
+    CommandError::from_solution_status(day, status)
 }
@@ -0,0 +1,260 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::answers::Answers;
+use crate::template::commands::CommandError;
+use crate::template::config::Config;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{
+    all_days, read_file, Day, InputError, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+    ANSI_YELLOW,
+};
+
+/// Answers longer than this are truncated in the table.
+const MAX_ANSWER_WIDTH: usize = 20;
+
+/// Puzzles unlock at midnight EST, i.e. 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// A single cell of the status table.
+struct Cell {
+    text: String,
+    colour: &'static str,
+}
+
+impl Cell {
+    fn new(text: impl Into<String>, colour: &'static str) -> Self {
+        Cell {
+            text: text.into(),
+            colour,
+        }
+    }
+
+    fn empty() -> Self {
+        Cell::new("-", ANSI_DIM)
+    }
+
+    fn width(&self) -> usize {
+        self.text.chars().count()
+    }
+}
+
+/// Prints an overview of all days: which files exist, whether tests pass, recorded answers, timings and unlock times.
+pub fn handle(run_tests: bool) -> Result<(), CommandError> {
+    let answers = Answers::read_from_file();
+    let timings = Timings::read_from_file();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let year = Config::get().year();
+
+    let header = [
+        "Day", "Bin", "Input", "Example", "Tests", "Part 1", "Part 2", "Timings", "Unlock",
+    ]
+    .map(|title| Cell::new(title, ANSI_BOLD));
+
+    let mut rows = vec![header.into_iter().collect::<Vec<_>>()];
+
+    for day in all_days() {
+        let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+
+        let tests = if !scaffolded || !run_tests {
+            Cell::empty()
+        } else {
+            eprint!("\rRunning tests for day {day}...");
+            let _ = std::io::stderr().flush();
+            test_cell(day)?
+        };
+
+        let timing = timings.data.iter().find(|t| t.day == day);
+        let timings = match timing {
+            Some(t) => Cell::new(
+                format!(
+                    "{} / {}",
+                    t.part_1.as_deref().unwrap_or("-"),
+                    t.part_2.as_deref().unwrap_or("-")
+                ),
+                "",
+            ),
+            None => Cell::empty(),
+        };
+
+        rows.push(vec![
+            Cell::new(day.to_string(), ANSI_BOLD),
+            if scaffolded {
+                Cell::new("✓", ANSI_GREEN)
+            } else {
+                Cell::new("✗", ANSI_DIM)
+            },
+            file_cell(read_file("inputs", day)),
+            file_cell(read_file("examples", day)),
+            tests,
+            answer_cell(answers.get(day, 1)),
+            answer_cell(answers.get(day, 2)),
+            timings,
+            unlock_cell(year, day, now),
+        ]);
+    }
+
+    if run_tests {
+        eprint!("\r\x1b[K");
+    }
+
+    print_table(&rows);
+    Ok(())
+}
+
+fn file_cell(result: Result<String, InputError>) -> Cell {
+    match result {
+        Ok(_) => Cell::new("✓", ANSI_GREEN),
+        Err(InputError::Missing(_)) => Cell::new("missing", ANSI_DIM),
+        Err(InputError::Empty(_)) => Cell::new("empty", ANSI_YELLOW),
+        Err(_) => Cell::new("invalid", ANSI_RED),
+    }
+}
+
+fn answer_cell(answer: Option<&str>) -> Cell {
+    let Some(answer) = answer else {
+        return Cell::empty();
+    };
+
+    let first_line = answer.lines().next().unwrap_or_default();
+    let text = if first_line.chars().count() > MAX_ANSWER_WIDTH || answer.contains('\n') {
+        let truncated: String = first_line.chars().take(MAX_ANSWER_WIDTH - 1).collect();
+        format!("{truncated}…")
+    } else {
+        first_line.to_string()
+    };
+
+    Cell::new(text, "")
+}
+
+/// Runs the tests of a day, reporting how many of them passed.
+fn test_cell(day: Day) -> Result<Cell, CommandError> {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| CommandError::Cargo(format!("failed to call cargo: {e}")))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(match parse_test_summary(&stdout) {
+        Some((passed, 0)) => Cell::new(format!("{passed} passed"), ANSI_GREEN),
+        Some((_, failed)) => Cell::new(format!("{failed} failed"), ANSI_RED),
+        None => Cell::new("build failed", ANSI_RED),
+    })
+}
+
+/// Parses the number of passed and failed tests from a `test result: ... N passed; M failed; ...` line.
+fn parse_test_summary(output: &str) -> Option<(u32, u32)> {
+    let summary = output
+        .lines()
+        .find_map(|line| line.strip_prefix("test result: "))?;
+
+    let count = |label: &str| {
+        summary.split(';').find_map(|s| {
+            s.trim()
+                .strip_suffix(label)?
+                .rsplit(' ')
+                .next()?
+                .parse()
+                .ok()
+        })
+    };
+
+    Some((count(" passed")?, count(" failed")?))
+}
+
+fn unlock_cell(year: Option<u16>, day: Day, now: u64) -> Cell {
+    let Some(year) = year else {
+        return Cell::empty();
+    };
+
+    let unlock = unlock_time(year, day);
+    if now >= unlock {
+        return Cell::new("unlocked", ANSI_DIM);
+    }
+
+    let remaining = unlock - now;
+    let (days, hours, minutes) = (
+        remaining / 86_400,
+        remaining % 86_400 / 3600,
+        remaining % 3600 / 60,
+    );
+    Cell::new(format!("in {days}d {hours:02}h {minutes:02}m"), ANSI_YELLOW)
+}
+
+/// Seconds since the unix epoch at which the puzzle of a day unlocks.
+fn unlock_time(year: u16, day: Day) -> u64 {
+    days_from_civil(u64::from(year), 12, u64::from(day.into_inner())) * 86_400
+        + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Days since the unix epoch of a date in the gregorian calendar.
+/// See: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn print_table(rows: &[Vec<Cell>]) {
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col].width()).max().unwrap_or(0))
+        .collect();
+
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = " ".repeat(width - cell.width());
+                format!("{}{}{ANSI_RESET}{padding}", cell.colour, cell.text)
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days_from_civil, parse_test_summary, unlock_time};
+    use crate::day;
+
+    #[test]
+    fn computes_days_since_epoch() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2024, 12, 1), 20_058);
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(2024, day!(1)), 1_733_029_200);
+        assert_eq!(unlock_time(2024, day!(2)), 1_733_029_200 + 86_400);
+    }
+
+    #[test]
+    fn parses_test_summaries() {
+        let output = "\nrunning 2 tests\n..\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        assert_eq!(parse_test_summary(output), Some((2, 0)));
+        let output =
+            "test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out";
+        assert_eq!(parse_test_summary(output), Some((1, 1)));
+        assert_eq!(parse_test_summary("error: could not compile"), None);
+    }
+}
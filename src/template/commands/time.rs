@@ -1,10 +1,14 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, env, fs, path::Path};
 
-use crate::template::run_multi::run_multi;
+use crate::template::answers::Answers;
+use crate::template::commands::CommandError;
+use crate::template::generators;
+use crate::template::run_multi::{child_commands, run_multi};
+use crate::template::scaling::{self, Sample};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, InputSource, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(day: Option<Day>, run_all: bool, store: bool) -> Result<(), CommandError> {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,20 +25,109 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let (timings, answers, result) = run_multi(&days_to_run, true, true);
+    let timings = timings.unwrap_or_default();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(|e| CommandError::IO("Failed to store timings".into(), e))?;
+
+        Answers::read_from_file()
+            .merge(&answers)
+            .store_file()
+            .map_err(|e| CommandError::IO("Failed to store answers".into(), e))?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        readme_benchmarks::update(merged_timings)
+            .map_err(|e| CommandError::IO("Failed to store updated benchmarks".into(), e.into()))?;
+        println!("Stored updated benchmarks.");
+    }
+
+    result
+}
+
+/// Benchmarks a day on generated inputs of increasing size and fits how its run time grows with the input size.
+pub fn handle_scale(
+    day: Day,
+    steps: u32,
+    seed: u64,
+    export: Option<&Path>,
+) -> Result<(), CommandError> {
+    let generator = generators::generator(day).ok_or_else(|| {
+        CommandError::Usage(format!("there is no input generator for day {day}."))
+    })?;
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} (seed {seed})");
+    println!("------");
+    println!(
+        "{:>8}  {:>10}  {:>12}  {:>12}",
+        "Size", "Bytes", "Part 1", "Part 2"
+    );
+
+    let mut samples = vec![];
+    for size in scaling::sizes(generator.default_size, steps) {
+        let path = env::temp_dir().join(format!("aoc-scale-{day}-{size}.txt"));
+        let input = generator.generate(size, seed);
+        fs::write(&path, &input)
+            .map_err(|e| CommandError::IO("Failed to write generated input".into(), e))?;
+
+        let run =
+            child_commands::capture_solution(day, true, true, &InputSource::File(path.clone()));
+        let _ = fs::remove_file(&path);
+
+        let run = match run {
+            Ok(Some(run)) => run,
+            Ok(None) => return Err(CommandError::Usage(format!("day {day} is not solved yet."))),
+            Err(e) => {
+                return Err(CommandError::Crashed(
+                    day,
+                    format!("could not run solution: {e}"),
+                ))
             }
+        };
+        if let Some(reason) = run.crash_reason() {
+            return Err(CommandError::Crashed(day, reason));
         }
+        CommandError::from_solution_status(day, run.status)?;
+
+        let nanos = child_commands::parse_part_nanos(&run.output);
+        let sample = Sample {
+            size,
+            bytes: input.len(),
+            part_1: nanos.get(&1).copied(),
+            part_2: nanos.get(&2).copied(),
+        };
+        println!(
+            "{size:>8}  {:>10}  {:>12}  {:>12}",
+            sample.bytes,
+            scaling::format_nanos(sample.part_1),
+            scaling::format_nanos(sample.part_2)
+        );
+        samples.push(sample);
     }
+
+    println!();
+    for part in [1, 2] {
+        if !scaling::inputs_grow(&samples) {
+            println!("Part {part}: n/a, the generated inputs do not differ in length.");
+            continue;
+        }
+        match scaling::fit_exponent(&samples, part) {
+            Some(exponent) => println!(
+                "Part {part}: time grows with bytes^{exponent:.2} ({})",
+                scaling::describe_exponent(exponent)
+            ),
+            None => println!("Part {part}: not enough samples to fit a curve."),
+        }
+    }
+
+    if let Some(export) = export {
+        fs::write(export, scaling::to_csv(&samples))
+            .map_err(|e| CommandError::IO("Failed to export samples".into(), e))?;
+        println!("\nExported samples to \"{}\".", export.display());
+    }
+
+    Ok(())
 }
@@ -0,0 +1,522 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::template::answers::{Answers, DayAnswers};
+use crate::template::commands::{download, solve, CommandError};
+use crate::template::config::Config;
+use crate::template::readme_benchmarks;
+use crate::template::run_multi::child_commands::{
+    capture_solution, parse_answers, parse_exec_time, SolutionRun,
+};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    all_days, Day, InputSource, ANSI_BOLD, ANSI_CLEAR_SCREEN, ANSI_ITALIC, ANSI_RESET,
+};
+
+const ANSI_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const ANSI_MAIN_SCREEN: &str = "\x1b[?1049l";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+const ANSI_REVERSE: &str = "\x1b[7m";
+
+const DAY_LIST_WIDTH: usize = 10;
+const HELP: &str = "↑↓ select  e example  r run  t time  w store  p puzzle  J/K scroll  d download  1/2 submit  q quit";
+
+/// Puts the terminal into raw mode on the alternate screen, restoring it when dropped.
+struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enter() -> Result<Self, CommandError> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        // reads return after at most 100ms so a bare Esc can be told apart from an escape sequence.
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        print!("{ANSI_ALTERNATE_SCREEN}{ANSI_HIDE_CURSOR}");
+        io::stdout()
+            .flush()
+            .map_err(|e| CommandError::IO("Failed to write to terminal".into(), e))?;
+        Ok(RawTerminal { saved })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("{ANSI_SHOW_CURSOR}{ANSI_MAIN_SCREEN}");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Calls `stty` on the terminal attached to stdin.
+fn stty(args: &[&str]) -> Result<String, CommandError> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| CommandError::IO("Failed to call stty".into(), e))?;
+
+    if !output.status.success() {
+        return Err(CommandError::Usage(
+            "`tui` requires an interactive terminal.".into(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns the number of rows and columns of the terminal.
+fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .ok()
+        .and_then(|size| {
+            let (rows, cols) = size.trim().split_once(' ')?;
+            Some((rows.parse().ok()?, cols.parse().ok()?))
+        })
+        // pseudo terminals may not report a size.
+        .filter(|&(rows, cols)| rows > 0 && cols > 0)
+        .unwrap_or((24, 80))
+}
+
+enum Key {
+    Up,
+    Down,
+    Char(char),
+    Other,
+}
+
+/// Reads a single byte, returning `None` if none arrived within the raw mode read timeout.
+fn read_byte(stdin: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    Ok(match stdin.read(&mut byte)? {
+        0 => None,
+        _ => Some(byte[0]),
+    })
+}
+
+fn read_key() -> io::Result<Key> {
+    let mut stdin = io::stdin().lock();
+    let byte = loop {
+        if let Some(byte) = read_byte(&mut stdin)? {
+            break byte;
+        }
+    };
+
+    Ok(match byte {
+        // a bare Esc is not followed by the rest of an escape sequence.
+        0x1b => match read_byte(&mut stdin)? {
+            Some(b'[') => match read_byte(&mut stdin)? {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                _ => Key::Other,
+            },
+            _ => Key::Other,
+        },
+        // Ctrl-C does not raise a signal in raw mode.
+        0x03 => Key::Char('q'),
+        byte => Key::Char(char::from(byte)),
+    })
+}
+
+enum View {
+    Results,
+    Puzzle,
+}
+
+/// The last run of a solution started from the TUI.
+struct LastRun {
+    day: Day,
+    input: InputSource,
+    is_timed: bool,
+    run: Result<Option<SolutionRun>, String>,
+}
+
+impl LastRun {
+    fn timing(&self) -> Option<Timing> {
+        match &self.run {
+            Ok(Some(run)) if self.is_timed && run.status.success() => {
+                Some(parse_exec_time(&run.output, self.day))
+            }
+            _ => None,
+        }
+    }
+}
+
+struct App {
+    days: Vec<Day>,
+    selected: usize,
+    view: View,
+    scroll: usize,
+    timings: Timings,
+    last_run: Option<LastRun>,
+    message: String,
+}
+
+/// A line of the right-hand pane, rendered with a single style.
+type Line = (&'static str, String);
+
+/// Browses all days in a full-screen terminal UI.
+/// Solutions can be run against the example or the real input, timed, stored and submitted.
+pub fn handle() -> Result<(), CommandError> {
+    let mut app = App {
+        days: all_days().collect(),
+        selected: 0,
+        view: View::Results,
+        scroll: 0,
+        timings: Timings::read_from_file(),
+        last_run: None,
+        message: HELP.into(),
+    };
+
+    let mut terminal = Some(RawTerminal::enter()?);
+
+    loop {
+        app.render()
+            .map_err(|e| CommandError::IO("Failed to write to terminal".into(), e))?;
+
+        let key = read_key().map_err(|e| CommandError::IO("Failed to read key".into(), e))?;
+        let day = app.days[app.selected];
+        app.message = HELP.into();
+
+        match key {
+            Key::Up | Key::Char('k') => app.select(app.selected.saturating_sub(1)),
+            Key::Down | Key::Char('j') => app.select(app.selected + 1),
+            Key::Char('K') => app.scroll = app.scroll.saturating_sub(5),
+            Key::Char('J') => app.scroll += 5,
+            Key::Char('p') => {
+                app.view = match app.view {
+                    View::Results => View::Puzzle,
+                    View::Puzzle => View::Results,
+                };
+                app.scroll = 0;
+            }
+            Key::Char('e') => app.run(day, InputSource::Example, false),
+            Key::Char('r') => app.run(day, InputSource::Puzzle, false),
+            Key::Char('t') => app.run(day, InputSource::Puzzle, true),
+            Key::Char('w') => app.store(),
+            Key::Char(part @ ('1' | '2')) => {
+                let part = part.to_digit(10).and_then(|p| u8::try_from(p).ok());
+                suspend(&mut terminal, || {
+                    solve::handle(
+                        day,
+                        true,
+                        false,
+                        part,
+                        &InputSource::Puzzle,
+                        false,
+                        false,
+                        None,
+                    )
+                })?;
+            }
+            Key::Char('d') => {
+                suspend(&mut terminal, || download::handle(day))?;
+            }
+            Key::Char('q') => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Leaves the TUI to run a command that interacts with the terminal, returning once enter is pressed.
+fn suspend(
+    terminal: &mut Option<RawTerminal>,
+    command: impl FnOnce() -> Result<(), CommandError>,
+) -> Result<(), CommandError> {
+    terminal.take();
+
+    if let Err(e) = command() {
+        eprintln!("Error: {e}");
+    }
+
+    println!();
+    println!("{ANSI_ITALIC}Press enter to return.{ANSI_RESET}");
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .map_err(|e| CommandError::IO("Failed to read from stdin".into(), e))?;
+
+    *terminal = Some(RawTerminal::enter()?);
+    Ok(())
+}
+
+impl App {
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.days.len() - 1);
+        self.scroll = 0;
+    }
+
+    fn run(&mut self, day: Day, input: InputSource, is_timed: bool) {
+        self.view = View::Results;
+        self.scroll = 0;
+        self.message = format!("Running day {day} against the {input}...");
+        // the run blocks, so show the message before starting it.
+        let _ = self.render();
+
+        let run = capture_solution(day, is_timed, true, &input).map_err(|e| e.to_string());
+        let last_run = LastRun {
+            day,
+            input,
+            is_timed,
+            run,
+        };
+
+        self.message = match &last_run.run {
+            Ok(None) => format!("Day {day} is not scaffolded yet."),
+            Ok(Some(_)) if last_run.timing().is_some() => "Press `w` to store the timings.".into(),
+            Ok(Some(_)) => HELP.into(),
+            Err(e) => format!("Could not run solution: {e}"),
+        };
+        self.last_run = Some(last_run);
+    }
+
+    /// Stores the timings and answers of the last timed run, like `cargo time --store`.
+    fn store(&mut self) {
+        let Some((last_run, timing)) = self
+            .last_run
+            .as_ref()
+            .and_then(|last_run| Some((last_run, last_run.timing()?)))
+        else {
+            self.message = "Time a solution with `t` before storing it.".into();
+            return;
+        };
+
+        let timings = self.timings.merge(&Timings { data: vec![timing] });
+
+        let answers = match &last_run.run {
+            Ok(Some(run)) => vec![DayAnswers::from_parsed(
+                last_run.day,
+                &parse_answers(&run.output),
+            )],
+            _ => vec![],
+        };
+
+        let result = timings
+            .store_file()
+            .and_then(|()| {
+                Answers::read_from_file()
+                    .merge(&Answers { data: answers })
+                    .store_file()
+            })
+            .and_then(|()| readme_benchmarks::update(timings.clone()).map_err(Into::into));
+
+        self.message = match result {
+            Ok(()) => format!("Stored timings for day {}.", last_run.day),
+            Err(e) => format!("Failed to store timings: {e}"),
+        };
+        self.timings = timings;
+    }
+
+    fn render(&self) -> io::Result<()> {
+        let (rows, cols) = terminal_size();
+        let body_height = rows.saturating_sub(2);
+        let pane_width = cols.saturating_sub(DAY_LIST_WIDTH + 3);
+
+        let mut pane = match self.view {
+            View::Results => self.results_pane(),
+            View::Puzzle => self.puzzle_pane(pane_width),
+        };
+        let scroll = self.scroll.min(pane.len().saturating_sub(1));
+        pane.drain(..scroll);
+
+        let list_offset = self.selected.saturating_sub(body_height.saturating_sub(1));
+
+        let mut frame = String::from(ANSI_CLEAR_SCREEN);
+        let year = Config::get()
+            .year()
+            .map_or(String::new(), |year| format!(" {year}"));
+        frame.push_str(&format!(
+            "{ANSI_BOLD}{}{ANSI_RESET}\r\n",
+            truncate(&format!("Advent of Code{year}"), cols)
+        ));
+
+        for row in 0..body_height {
+            let entry = match self.days.get(list_offset + row) {
+                Some(day) => {
+                    let text = format!(" {} {}", day, self.day_markers(*day));
+                    let style = if list_offset + row == self.selected {
+                        ANSI_REVERSE
+                    } else {
+                        ""
+                    };
+                    format!("{style}{text:<DAY_LIST_WIDTH$}{ANSI_RESET}")
+                }
+                None => " ".repeat(DAY_LIST_WIDTH),
+            };
+
+            let (style, line) = pane.get(row).cloned().unwrap_or_default();
+            frame.push_str(&format!(
+                "{entry} │ {style}{}{ANSI_RESET}\r\n",
+                truncate(&line, pane_width)
+            ));
+        }
+
+        frame.push_str(&format!(
+            "{ANSI_ITALIC}{}{ANSI_RESET}",
+            truncate(&self.message, cols)
+        ));
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(frame.as_bytes())?;
+        stdout.flush()
+    }
+
+    /// `✓` if the day is scaffolded, `★` if both parts are benchmarked.
+    fn day_markers(&self, day: Day) -> String {
+        let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+        let benched = self.timings.is_day_complete(day);
+        format!(
+            "{}{}",
+            if scaffolded { "✓" } else { " " },
+            if benched { "★" } else { " " }
+        )
+    }
+
+    fn results_pane(&self) -> Vec<Line> {
+        let day = self.days[self.selected];
+        let mut lines: Vec<Line> = vec![(ANSI_BOLD, format!("Day {day}")), ("", String::new())];
+
+        let last_run = self.last_run.as_ref().filter(|r| r.day == day);
+
+        match last_run.map(|r| (r, &r.run)) {
+            None => lines.push((
+                ANSI_ITALIC,
+                "No runs yet. Press `e` to run the example or `r` to run the puzzle input.".into(),
+            )),
+            Some((_, Err(e))) => lines.push(("", format!("Could not run solution: {e}"))),
+            Some((_, Ok(None))) => lines.push(("", "Not scaffolded yet.".into())),
+            Some((last_run, Ok(Some(run)))) => {
+                lines.push((ANSI_ITALIC, format!("Ran against the {}.", last_run.input)));
+                lines.push(("", String::new()));
+
+                for (part, answer) in parse_answers(&run.output) {
+                    let mut answer_lines = answer.lines();
+                    let first = answer_lines.next().unwrap_or_default();
+                    lines.push(("", format!("Part {part}: {first}")));
+                    lines.extend(answer_lines.map(|l| ("", format!("        {l}"))));
+                }
+
+                let status = match (
+                    run.crash_reason(),
+                    CommandError::from_solution_status(day, run.status),
+                ) {
+                    (Some(reason), _) => Some(format!("Crashed: {reason}")),
+                    (None, Err(e)) => Some(format!("Error: {e}")),
+                    (None, Ok(())) => None,
+                };
+
+                if let Some(status) = status {
+                    lines.push(("", String::new()));
+                    lines.push((ANSI_BOLD, status));
+                    let start = run.errors.len().saturating_sub(8);
+                    lines.extend(run.errors[start..].iter().map(|l| ("", l.clone())));
+                }
+            }
+        }
+
+        let latest = last_run.and_then(LastRun::timing);
+        let stored = self.timings.data.iter().find(|t| t.day == day);
+
+        if latest.is_some() || stored.is_some() {
+            lines.push(("", String::new()));
+            lines.push((
+                ANSI_BOLD,
+                format!("{:<10}{:<16}{:<16}", "Timings", "Last run", "Stored"),
+            ));
+            for part in [1, 2] {
+                let get = |timing: Option<&Timing>| {
+                    timing
+                        .and_then(|t| {
+                            if part == 1 {
+                                t.part_1.clone()
+                            } else {
+                                t.part_2.clone()
+                            }
+                        })
+                        .unwrap_or_else(|| "-".into())
+                };
+                lines.push((
+                    "",
+                    format!(
+                        "{:<10}{:<16}{:<16}",
+                        format!("Part {part}"),
+                        get(latest.as_ref()),
+                        get(stored)
+                    ),
+                ));
+            }
+        }
+
+        lines
+    }
+
+    fn puzzle_pane(&self, width: usize) -> Vec<Line> {
+        let day = self.days[self.selected];
+        let path = Config::get().puzzle_path(day);
+
+        match fs::read_to_string(&path) {
+            Ok(puzzle) => puzzle
+                .lines()
+                .flat_map(|line| wrap(line, width))
+                .map(|line| {
+                    let style = if line.starts_with('#') { ANSI_BOLD } else { "" };
+                    (style, line)
+                })
+                .collect(),
+            Err(_) => vec![(
+                ANSI_ITALIC,
+                format!(
+                    "No puzzle description at \"{}\". Press `d` to download it.",
+                    path.display()
+                ),
+            )],
+        }
+    }
+}
+
+/// Truncates a line to fit into a number of columns.
+fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+/// Wraps a line at word boundaries so that it fits into a number of columns.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+
+    for word in line.split(' ') {
+        let current = lines.last_mut().unwrap();
+        let len = current.chars().count();
+        if len > 0 && len + 1 + word.chars().count() > width {
+            lines.push(word.to_string());
+        } else {
+            if len > 0 {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+    }
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::wrap;
+
+    #[test]
+    fn wraps_lines_at_word_boundaries() {
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("one two three", 8), vec!["one two", "three"]);
+        assert_eq!(wrap("averylongword x", 4), vec!["averylongword", "x"]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::commands::CommandError;
+use crate::template::config::Config;
+use crate::template::run_multi::{child_commands::parse_answers, get_path_for_bin};
+use crate::template::{Day, InputSource, ANSI_BOLD, ANSI_CLEAR_SCREEN, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Re-runs the solution (or its tests) for a day whenever its source or input files change.
+pub fn handle(
+    day: Day,
+    release: bool,
+    test: bool,
+    input: &InputSource,
+) -> Result<(), CommandError> {
+    let config = Config::get();
+    let mut watched = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        config.example_path(day),
+        config.input_path(day),
+    ];
+
+    if let InputSource::File(path) = input {
+        watched.push(path.clone());
+    }
+
+    let mut last_modified = modified_times(&watched);
+    let mut previous: Option<BTreeMap<String, String>> = None;
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        let mode = if test { "tests" } else { "solution" };
+        println!("{ANSI_BOLD}Watching {mode} for day {day}{ANSI_RESET} {ANSI_ITALIC}(Ctrl-C to stop){ANSI_RESET}");
+        println!();
+
+        let results = run(day, release, test, input)?;
+
+        match &results {
+            Some(results) => print_diff(previous.as_ref(), results),
+            None => println!("{ANSI_BOLD}Build failed.{ANSI_RESET}"),
+        }
+
+        if results.is_some() {
+            previous = results;
+        }
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let modified = modified_times(&watched);
+            if modified != last_modified {
+                last_modified = modified;
+                break;
+            }
+        }
+    }
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Runs the solution or tests once, returning the answers or test results by name.
+/// Returns `None` if the solution could not be built.
+fn run(
+    day: Day,
+    release: bool,
+    test: bool,
+    input: &InputSource,
+) -> Result<Option<BTreeMap<String, String>>, CommandError> {
+    let day_padded = day.to_string();
+    // NOTE: `--quiet` would switch the test harness to its terse output format.
+    let mut args: Vec<String> = if test {
+        vec!["test".into(), "--bin".into(), day_padded]
+    } else {
+        vec!["run".into(), "--quiet".into(), "--bin".into(), day_padded]
+    };
+
+    if release {
+        args.push("--release".into());
+    }
+
+    if !test {
+        args.push("--".into());
+        args.extend(input.to_args());
+    }
+
+    let output = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| CommandError::Cargo(format!("failed to call cargo: {e}")))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+    println!();
+
+    let lines: Vec<String> = stdout.lines().map(ToString::to_string).collect();
+
+    let results = if test {
+        parse_test_results(&lines)
+    } else {
+        parse_answers(&lines)
+            .into_iter()
+            .map(|(part, answer)| (format!("Part {part}"), answer))
+            .collect()
+    };
+
+    // a failed build produces neither answers nor test results.
+    if results.is_empty() && !output.status.success() {
+        Ok(None)
+    } else {
+        Ok(Some(results))
+    }
+}
+
+/// Parses the `test <name> ... <status>` lines printed by `cargo test`.
+fn parse_test_results(lines: &[String]) -> BTreeMap<String, String> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let (name, status) = line.strip_prefix("test ")?.rsplit_once(" ... ")?;
+            Some((name.to_string(), status.to_string()))
+        })
+        .collect()
+}
+
+fn print_diff(previous: Option<&BTreeMap<String, String>>, results: &BTreeMap<String, String>) {
+    let Some(previous) = previous else {
+        return;
+    };
+
+    println!("{ANSI_BOLD}Changes since last run{ANSI_RESET}");
+    println!("----------------------");
+
+    for (name, result) in results {
+        match previous.get(name) {
+            Some(prev) if prev == result => {
+                println!("{name}: {result} {ANSI_ITALIC}(unchanged){ANSI_RESET}");
+            }
+            Some(prev) => println!("{name}: {ANSI_BOLD}{result}{ANSI_RESET} (was {prev})"),
+            None => println!("{name}: {ANSI_BOLD}{result}{ANSI_RESET} (new)"),
+        }
+    }
+
+    for name in previous.keys().filter(|name| !results.contains_key(*name)) {
+        println!("{name}: {ANSI_ITALIC}(removed){ANSI_RESET}");
+    }
+}
@@ -0,0 +1,273 @@
+/// Project configuration, read from an optional `aoc.toml` file in the project root.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::Day;
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Settings of the template. Every setting is optional and defaults to the standard layout.
+///
+/// ```toml
+/// year = 2024
+/// data_dir = "data"
+/// readme = "README.md"
+/// timings = "data/timings.json"
+/// answers = "data/answers.json"
+/// bench_budget_ms = 1000
+/// template = "src/template.txt"
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year of advent of code to solve. The `AOC_YEAR` environment variable takes precedence.
+    pub year: Option<u16>,
+    /// Directory containing the `inputs`, `examples` and `puzzles` folders.
+    pub data_dir: PathBuf,
+    /// Readme that benchmarks are written to.
+    pub readme_path: PathBuf,
+    /// File that benchmark timings are stored in.
+    pub timings_path: PathBuf,
+    /// File that the answers of solutions are recorded in.
+    pub answers_path: PathBuf,
+    /// Approximate time spent benchmarking each part.
+    pub bench_budget: Duration,
+    /// Template for scaffolded solutions. Uses the built-in template if not set.
+    pub template_path: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let data_dir = PathBuf::from("data");
+        Self {
+            year: None,
+            timings_path: data_dir.join("timings.json"),
+            answers_path: data_dir.join("answers.json"),
+            data_dir,
+            readme_path: PathBuf::from("README.md"),
+            bench_budget: Duration::from_secs(1),
+            template_path: None,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the configuration of the current project, reading `aoc.toml` on first access.
+    pub fn get() -> &'static Self {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+        CONFIG.get_or_init(Config::read_from_file)
+    }
+
+    /// Reads the configuration file. If not present, returns the default configuration.
+    fn read_from_file() -> Self {
+        let s = match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => s,
+            Err(_) => return Config::default(),
+        };
+
+        match Config::from_str(&s) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to parse {CONFIG_FILE_PATH}: {e}");
+                Config::default()
+            }
+        }
+    }
+
+    /// The year to solve, preferring the `AOC_YEAR` environment variable over the config file.
+    pub fn year(&self) -> Option<u16> {
+        env::var("AOC_YEAR")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .or(self.year)
+    }
+
+    /// Path of a file for a day in one of the data folders, e.g. `data/inputs/01.txt`.
+    pub fn day_path(&self, folder: &str, day: Day, extension: &str) -> PathBuf {
+        self.data_dir
+            .join(folder)
+            .join(format!("{day}.{extension}"))
+    }
+
+    pub fn input_path(&self, day: Day) -> PathBuf {
+        self.day_path("inputs", day, "txt")
+    }
+
+    pub fn example_path(&self, day: Day) -> PathBuf {
+        self.day_path("examples", day, "txt")
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> PathBuf {
+        self.day_path("puzzles", day, "md")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Config`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigError {
+    line: usize,
+    message: String,
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// The subset of TOML values the config file supports.
+enum Value {
+    String(String),
+    Integer(u64),
+}
+
+impl FromStr for Config {
+    type Err = ConfigError;
+
+    /// Parses a flat TOML document of `key = value` pairs.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = Config::default();
+        let mut timings_path = None;
+        let mut answers_path = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let error = |message: String| ConfigError {
+                line: index + 1,
+                message,
+            };
+
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected a `key = value` pair.".into()))?;
+            let key = key.trim();
+            let value = parse_value(value.trim()).map_err(error)?;
+
+            match (key, value) {
+                ("year", Value::Integer(year)) => {
+                    config.year = Some(
+                        u16::try_from(year)
+                            .map_err(|_| error(format!("invalid year `{year}`.")))?,
+                    );
+                }
+                ("data_dir", Value::String(path)) => config.data_dir = path.into(),
+                ("readme", Value::String(path)) => config.readme_path = path.into(),
+                ("timings", Value::String(path)) => timings_path = Some(PathBuf::from(path)),
+                ("answers", Value::String(path)) => answers_path = Some(PathBuf::from(path)),
+                ("bench_budget_ms", Value::Integer(millis)) => {
+                    config.bench_budget = Duration::from_millis(millis);
+                }
+                ("template", Value::String(path)) => config.template_path = Some(path.into()),
+                ("year" | "bench_budget_ms", _) => {
+                    return Err(error(format!("expected `{key}` to be an integer.")))
+                }
+                ("data_dir" | "readme" | "timings" | "answers" | "template", _) => {
+                    return Err(error(format!("expected `{key}` to be a string.")))
+                }
+                _ => return Err(error(format!("unknown key `{key}`."))),
+            }
+        }
+
+        config.timings_path = timings_path.unwrap_or_else(|| config.data_dir.join("timings.json"));
+        config.answers_path = answers_path.unwrap_or_else(|| config.data_dir.join("answers.json"));
+        Ok(config)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    if let Some(inner) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        if inner.contains('"') || inner.contains('\\') {
+            return Err(format!(
+                "unsupported string `{s}`, escapes are not supported."
+            ));
+        }
+        return Ok(Value::String(inner.into()));
+    }
+
+    s.replace('_', "")
+        .parse()
+        .map(Value::Integer)
+        .map_err(|_| format!("expected a string or a positive integer, got `{s}`."))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, str::FromStr, time::Duration};
+
+    use super::Config;
+
+    #[test]
+    fn defaults_to_standard_layout() {
+        let config = Config::from_str("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.data_dir, PathBuf::from("data"));
+        assert_eq!(config.timings_path, PathBuf::from("data/timings.json"));
+        assert_eq!(config.readme_path, PathBuf::from("README.md"));
+        assert_eq!(config.bench_budget, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn parses_settings() {
+        let config = Config::from_str(
+            r#"
+            # solving a previous year.
+            year = 2023
+            data_dir = "puzzle-data" # inline comment
+            readme = "docs/README.md"
+            bench_budget_ms = 2_500
+            template = "templates/#day.txt"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.data_dir, PathBuf::from("puzzle-data"));
+        assert_eq!(
+            config.timings_path,
+            PathBuf::from("puzzle-data/timings.json")
+        );
+        assert_eq!(
+            config.answers_path,
+            PathBuf::from("puzzle-data/answers.json")
+        );
+        assert_eq!(config.readme_path, PathBuf::from("docs/README.md"));
+        assert_eq!(config.bench_budget, Duration::from_millis(2500));
+        assert_eq!(
+            config.template_path,
+            Some(PathBuf::from("templates/#day.txt"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert_eq!(Config::from_str("foo = 1").unwrap_err().line, 1);
+        assert_eq!(Config::from_str("\nyear = \"2024\"").unwrap_err().line, 2);
+        assert!(Config::from_str("data_dir = 1").is_err());
+        assert!(Config::from_str("year").is_err());
+    }
+}
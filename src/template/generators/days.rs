@@ -0,0 +1,626 @@
+/// Generators for the inputs of each solved day.
+///
+/// Each generator mirrors the structure of the real puzzle input and upholds the guarantees
+/// the puzzle makes about it, e.g. that the guard of day 6 eventually leaves the map.
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use super::rng::Rng;
+
+/// Two columns of location ids.
+pub(super) fn day_01(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let left = rng.range(10_000, 99_999);
+        // reuse ids of the left column so that the similarity score is not always zero.
+        let right = if rng.chance(0.3) {
+            left
+        } else {
+            rng.range(10_000, 99_999)
+        };
+        writeln!(output, "{left}   {right}").unwrap();
+    }
+    output
+}
+
+/// Reports of levels, mostly safe with some faulty levels mixed in.
+pub(super) fn day_02(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let len = rng.index(4) + 5;
+        let increasing = rng.chance(0.5);
+        let mut levels = vec![rng.range_i64(25, 75)];
+        for _ in 1..len {
+            let step = rng.range_i64(1, 3);
+            let last = *levels.last().unwrap();
+            levels.push(if increasing { last + step } else { last - step });
+        }
+
+        let faults = match rng.range(0, 19) {
+            0..=9 => 0,
+            10..=16 => 1,
+            _ => 2,
+        };
+        for _ in 0..faults {
+            let i = rng.index(len);
+            levels[i] = match rng.range(0, 2) {
+                // duplicate a neighbouring level.
+                0 => levels[if i == 0 { 1 } else { i - 1 }],
+                // jump too far.
+                1 => levels[i] + rng.range_i64(4, 7) * if rng.chance(0.5) { 1 } else { -1 },
+                // change direction.
+                _ => levels[i] + if increasing { -4 } else { 4 },
+            }
+            .max(1);
+        }
+
+        let line: Vec<String> = levels.iter().map(ToString::to_string).collect();
+        writeln!(output, "{}", line.join(" ")).unwrap();
+    }
+    output
+}
+
+/// Corrupted memory with `mul(a,b)`, `do()` and `don't()` instructions between garbage.
+pub(super) fn day_03(rng: &mut Rng, size: usize) -> String {
+    const JUNK_CHARS: &[char] = &[
+        '!', '@', '#', '$', '%', '^', '&', '*', '[', ']', '{', '}', '<', '>', '?', '\'', '+', '-',
+        '_', ':', ';', '~', ' ', ',', '(', ')',
+    ];
+    const JUNK_WORDS: &[&str] = &[
+        "why()", "what()", "from()", "select()", "where()", "how()", "who()", "when()",
+    ];
+    const TOKENS_PER_LINE: usize = 100;
+
+    let mut output = String::new();
+    for i in 0..size {
+        let (a, b) = (rng.range(1, 999), rng.range(1, 999));
+        let token = match rng.range(0, 9) {
+            0..=3 => format!("mul({a},{b})"),
+            4 => match rng.range(0, 4) {
+                0 => format!("mul({a},{b}]"),
+                1 => format!("mul[{a},{b})"),
+                2 => format!("mul ({a},{b})"),
+                3 => format!("mul({a}, {b})"),
+                _ => format!("mul({a}*"),
+            },
+            5 => "do()".into(),
+            6 => "don't()".into(),
+            7 => rng.choose(JUNK_WORDS).to_string(),
+            _ => (0..rng.range(1, 5))
+                .map(|_| *rng.choose(JUNK_CHARS))
+                .collect(),
+        };
+        output.push_str(&token);
+
+        if (i + 1) % TOKENS_PER_LINE == 0 || i + 1 == size {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// A letter grid made of `X`, `M`, `A` and `S`.
+pub(super) fn day_04(rng: &mut Rng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let line: String = (0..size)
+            .map(|_| *rng.choose(&['X', 'M', 'A', 'S']))
+            .collect();
+        writeln!(output, "{line}").unwrap();
+    }
+    output
+}
+
+/// Page ordering rules followed by updates, about half of which are in the right order.
+///
+/// The rules are derived from a single ordering of all pages, so they never contradict each other.
+pub(super) fn day_05(rng: &mut Rng, size: usize) -> String {
+    const PAGES: usize = 49;
+
+    let mut pages: Vec<u64> = (10..=99).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(PAGES);
+
+    let mut rules = vec![];
+    for i in 0..pages.len() {
+        for j in i + 1..pages.len() {
+            rules.push(format!("{}|{}", pages[i], pages[j]));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut output = rules.join("\n");
+    output.push_str("\n\n");
+
+    for _ in 0..size {
+        let len = rng.range(2, 11) as usize * 2 + 1;
+        let mut indices: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut indices);
+        indices.truncate(len);
+
+        if rng.chance(0.5) {
+            indices.sort_unstable();
+        }
+
+        let update: Vec<String> = indices.iter().map(|&i| pages[i].to_string()).collect();
+        writeln!(output, "{}", update.join(",")).unwrap();
+    }
+    output
+}
+
+/// A map with obstacles and a guard that eventually walks off the map.
+///
+/// Random maps mostly make the guard leave quickly or walk in a loop, so this generates a number
+/// of candidates and keeps the one where the guard visits the most tiles.
+pub(super) fn day_06(rng: &mut Rng, size: usize) -> String {
+    const CANDIDATES: usize = 200;
+
+    let size = size.max(2);
+    let mut best: Option<(usize, Vec<Vec<char>>)> = None;
+
+    for candidate in 0.. {
+        if candidate >= CANDIDATES && best.is_some() {
+            break;
+        }
+
+        let mut grid: Vec<Vec<char>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.chance(0.05) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        let start = (rng.index(size), rng.index(size));
+        grid[start.0][start.1] = '^';
+
+        if let Some(visited) = guard_path(&grid, start) {
+            if best.as_ref().is_none_or(|(most, _)| visited > *most) {
+                best = Some((visited, grid));
+            }
+        }
+    }
+
+    best.unwrap()
+        .1
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Simulates the guard of day 6, returning the number of visited tiles if it leaves the map
+/// instead of walking in a loop.
+fn guard_path(grid: &[Vec<char>], start: (usize, usize)) -> Option<usize> {
+    const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    let mut position = start;
+    let mut direction = 0;
+    let mut seen = HashSet::new();
+
+    while seen.insert((position, direction)) {
+        let (dr, dc) = DIRECTIONS[direction];
+        let next = (
+            position.0.checked_add_signed(dr),
+            position.1.checked_add_signed(dc),
+        );
+        match next {
+            (Some(r), Some(c)) if r < grid.len() && c < grid[r].len() => {
+                if grid[r][c] == '#' {
+                    direction = (direction + 1) % 4;
+                } else {
+                    position = (r, c);
+                }
+            }
+            _ => {
+                let visited: HashSet<_> = seen.iter().map(|(position, _)| position).collect();
+                return Some(visited.len());
+            }
+        }
+    }
+
+    None
+}
+
+/// Calibration equations, some of which can be solved with `+`, `*` and `||`.
+///
+/// Like in the real inputs, the test values stay below 10^15.
+pub(super) fn day_07(rng: &mut Rng, size: usize) -> String {
+    const MAX_TARGET: u128 = 1_000_000_000_000_000;
+
+    let mut output = String::new();
+    let mut equations = 0;
+    while equations < size {
+        let len = rng.range(2, 12) as usize;
+        let operands: Vec<u128> = (0..len)
+            .map(|_| {
+                if len <= 6 && rng.chance(0.2) {
+                    u128::from(rng.range(100, 999))
+                } else {
+                    u128::from(rng.range(1, 99))
+                }
+            })
+            .collect();
+
+        let mut target = operands[0];
+        for &operand in &operands[1..] {
+            target = match rng.range(0, 2) {
+                0 => target + operand,
+                1 => target * operand,
+                _ => target * 10u128.pow(operand.ilog10() + 1) + operand,
+            };
+        }
+
+        if rng.chance(0.3) {
+            target += u128::from(rng.range(1, 9));
+        }
+        if target >= MAX_TARGET {
+            continue;
+        }
+
+        let operands: Vec<String> = operands.iter().map(ToString::to_string).collect();
+        writeln!(output, "{target}: {}", operands.join(" ")).unwrap();
+        equations += 1;
+    }
+    output
+}
+
+/// A map of antennas, each frequency appearing a few times.
+pub(super) fn day_08(rng: &mut Rng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut grid = vec![vec!['.'; size]; size];
+    let antennas = size * size / 12;
+    let frequencies = (antennas / 4).clamp(1, FREQUENCIES.len());
+
+    for i in 0..antennas.min(size * size) {
+        loop {
+            let (r, c) = (rng.index(size), rng.index(size));
+            if grid[r][c] == '.' {
+                grid[r][c] = char::from(FREQUENCIES[i % frequencies]);
+                break;
+            }
+        }
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A disk map of alternating file and free space lengths, starting and ending with a file.
+pub(super) fn day_09(rng: &mut Rng, size: usize) -> String {
+    let len = if size.is_multiple_of(2) {
+        size + 1
+    } else {
+        size
+    };
+    let mut output: String = (0..len)
+        .map(|i| {
+            let digit = if i % 2 == 0 {
+                rng.range(1, 9)
+            } else {
+                rng.range(0, 9)
+            };
+            char::from_digit(digit as u32, 10).unwrap()
+        })
+        .collect();
+    output.push('\n');
+    output
+}
+
+/// A topographic map of hills, so that there are hiking trails from `0` to `9`.
+pub(super) fn day_10(rng: &mut Rng, size: usize) -> String {
+    let peaks: Vec<(usize, usize)> = (0..(size * size / 40).max(1))
+        .map(|_| (rng.index(size), rng.index(size)))
+        .collect();
+
+    let mut output = String::new();
+    for r in 0..size {
+        for c in 0..size {
+            let height = if rng.chance(0.1) {
+                rng.range(0, 9) as usize
+            } else {
+                let distance = peaks
+                    .iter()
+                    .map(|&(pr, pc)| pr.abs_diff(r) + pc.abs_diff(c))
+                    .min()
+                    .unwrap();
+                9usize.saturating_sub(distance)
+            };
+            output.push(char::from_digit(height as u32, 10).unwrap());
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// A line of engraved stones.
+pub(super) fn day_11(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let digits = rng.range(1, 7) as u32;
+            rng.range(0, 10u64.pow(digits) - 1).to_string()
+        })
+        .collect();
+    stones.join(" ") + "\n"
+}
+
+/// Claw machines, about half of which can win their prize.
+///
+/// Like in the real inputs, one button moves the claw further along X and the other further along
+/// Y, and every prize lies between the two directions. This means the equation system always has a
+/// unique solution which is never negative, not even after moving the prizes for part two.
+pub(super) fn day_13(rng: &mut Rng, size: usize) -> String {
+    let mut machines = vec![];
+
+    while machines.len() < size {
+        let (ax, ay) = (rng.range(10, 99), rng.range(10, 99));
+        let (bx, by) = (rng.range(10, 99), rng.range(10, 99));
+        if ax == ay || bx == by || (ax > ay) == (bx > by) {
+            continue;
+        }
+
+        let (a, b) = (rng.range(1, 100), rng.range(1, 100));
+        let (mut px, mut py) = (a * ax + b * bx, a * ay + b * by);
+        if rng.chance(0.5) {
+            // nudge the prize, which most likely leaves it unreachable.
+            px += rng.range(1, 9);
+            py += rng.range(1, 9);
+        }
+
+        machines.push(format!(
+            "Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}\n"
+        ));
+    }
+
+    machines.join("\n")
+}
+
+/// Robots on a 101x103 grid, which all stand on distinct tiles for the first time after a random number of seconds.
+///
+/// Two stationary robots in opposing corners make sure the grid size can be inferred from the positions.
+pub(super) fn day_14(rng: &mut Rng, size: usize) -> String {
+    const WIDTH: i64 = 101;
+    const HEIGHT: i64 = 103;
+    const CORNERS: [(i64, i64); 2] = [(0, 0), (WIDTH - 1, HEIGHT - 1)];
+
+    let period = WIDTH * HEIGHT;
+    let picture_time = rng.range_i64(period / 10, period - 1);
+
+    let position_at = |(p, v): ((i64, i64), (i64, i64)), t: i64| {
+        (
+            (p.0 + v.0 * t).rem_euclid(WIDTH),
+            (p.1 + v.1 * t).rem_euclid(HEIGHT),
+        )
+    };
+    let random_velocity = |rng: &mut Rng| loop {
+        let v = (rng.range_i64(-99, 99), rng.range_i64(-99, 99));
+        if v.0 != 0 && v.1 != 0 {
+            return v;
+        }
+    };
+
+    // place the robots on distinct tiles at picture time and move them back to their starting positions.
+    let mut occupied: HashSet<(i64, i64)> = CORNERS.into_iter().collect();
+    let mut robots: Vec<((i64, i64), (i64, i64))> = CORNERS.iter().map(|&c| (c, (0, 0))).collect();
+
+    let free_tile = |rng: &mut Rng, occupied: &mut HashSet<(i64, i64)>| loop {
+        let tile = (rng.range_i64(0, WIDTH - 1), rng.range_i64(0, HEIGHT - 1));
+        if occupied.insert(tile) {
+            return tile;
+        }
+    };
+
+    for _ in 0..size {
+        let target = free_tile(rng, &mut occupied);
+        let v = random_velocity(rng);
+        robots.push((position_at((target, v), -picture_time), v));
+    }
+
+    // make sure the robots overlap at every earlier second by adding pairs of robots which meet.
+    let mut tiles = vec![usize::MAX; (WIDTH * HEIGHT) as usize];
+    for t in 1..picture_time {
+        let overlaps = robots.iter().any(|&robot| {
+            let (x, y) = position_at(robot, t);
+            let tile = &mut tiles[(y * WIDTH + x) as usize];
+            let overlap = *tile == t as usize;
+            *tile = t as usize;
+            overlap
+        });
+
+        if !overlaps {
+            let meeting = (rng.range_i64(1, WIDTH - 2), rng.range_i64(1, HEIGHT - 2));
+            for _ in 0..2 {
+                // pick a velocity that moves the robot to a free tile at picture time.
+                loop {
+                    let v = random_velocity(rng);
+                    let start = position_at((meeting, v), -t);
+                    if occupied.insert(position_at((start, v), picture_time)) {
+                        robots.push((start, v));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    rng.shuffle(&mut robots);
+    robots
+        .iter()
+        .map(|((px, py), (vx, vy))| format!("p={px},{py} v={vx},{vy}\n"))
+        .collect()
+}
+
+/// A chronospatial computer program that outputs a copy of itself for some value of register A.
+///
+/// The program follows the structure of the real inputs, which shift register A by three bits per output.
+pub(super) fn day_17(rng: &mut Rng, _size: usize) -> String {
+    loop {
+        let (k1, k2) = (rng.range(1, 7) as u8, rng.range(1, 7) as u8);
+
+        let mut middle = vec![vec![1, k2], vec![4, rng.range(0, 7) as u8]];
+        if rng.chance(0.5) {
+            middle.reverse();
+        }
+        middle.insert(rng.index(middle.len() + 1), vec![0, 3]);
+
+        let program: Vec<u8> = [vec![2, 4, 1, k1, 7, 5]]
+            .into_iter()
+            .chain(middle)
+            .chain([vec![5, 5, 3, 0]])
+            .flatten()
+            .collect();
+
+        if find_quine(&program).is_none() {
+            continue;
+        }
+
+        let register_a = rng.range(1 << 40, 1 << 47);
+        let program: Vec<String> = program.iter().map(ToString::to_string).collect();
+        return format!(
+            "Register A: {register_a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+            program.join(",")
+        );
+    }
+}
+
+/// Runs a day 17 program.
+fn run_program(program: &[u8], mut a: u64) -> Vec<u8> {
+    let (mut b, mut c) = (0u64, 0u64);
+    let mut pointer = 0;
+    let mut output = vec![];
+
+    while pointer + 1 < program.len() {
+        let operand = program[pointer + 1];
+        let combo = match operand {
+            0..=3 => u64::from(operand),
+            4 => a,
+            5 => b,
+            _ => c,
+        };
+
+        match program[pointer] {
+            0 => a = a.checked_shr(combo as u32).unwrap_or(0),
+            1 => b ^= u64::from(operand),
+            2 => b = combo % 8,
+            3 if a != 0 => {
+                pointer = usize::from(operand);
+                continue;
+            }
+            4 => b ^= c,
+            5 => output.push((combo % 8) as u8),
+            6 => b = a.checked_shr(combo as u32).unwrap_or(0),
+            7 => c = a.checked_shr(combo as u32).unwrap_or(0),
+            _ => {}
+        }
+        pointer += 2;
+    }
+
+    output
+}
+
+/// Finds the lowest value of register A for which a day 17 program outputs itself.
+fn find_quine(program: &[u8]) -> Option<u64> {
+    let mut candidates = vec![0u64];
+
+    for len in 1..=program.len() {
+        let expected = &program[program.len() - len..];
+        candidates = candidates
+            .iter()
+            .flat_map(|&a| (0..8).map(move |k| a * 8 + k))
+            .filter(|&a| run_program(program, a) == expected)
+            .collect();
+    }
+
+    candidates.into_iter().min()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_leave_the_map() {
+        for seed in 0..10 {
+            let input = day_06(&mut Rng::new(seed), 30);
+            let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+            let start = grid
+                .iter()
+                .enumerate()
+                .find_map(|(r, row)| Some((r, row.iter().position(|&c| c == '^')?)))
+                .unwrap();
+            assert!(guard_path(&grid, start).is_some());
+        }
+    }
+
+    #[test]
+    fn claw_machines_have_independent_buttons() {
+        let input = day_13(&mut Rng::new(1), 200);
+        for machine in input.split("\n\n") {
+            let numbers: Vec<i64> = machine
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|s| s.parse().ok())
+                .collect();
+            let [ax, ay, bx, by, _, _] = numbers[..] else {
+                panic!("unexpected machine {machine}");
+            };
+            assert_ne!(ax * by - ay * bx, 0);
+        }
+    }
+
+    #[test]
+    fn page_rules_are_consistent() {
+        let input = day_05(&mut Rng::new(2), 20);
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let rules: HashSet<(&str, &str)> =
+            rules.lines().map(|l| l.split_once('|').unwrap()).collect();
+        assert!(rules.iter().all(|(a, b)| !rules.contains(&(b, a))));
+        assert_eq!(updates.lines().count(), 20);
+    }
+
+    #[test]
+    fn robots_form_a_picture_once() {
+        let input = day_14(&mut Rng::new(3), 50);
+        let robots: Vec<Vec<i64>> = input
+            .lines()
+            .map(|l| {
+                l.split(|c: char| c != '-' && !c.is_ascii_digit())
+                    .filter_map(|s| s.parse().ok())
+                    .collect()
+            })
+            .collect();
+
+        let distinct_at = |t: i64| {
+            let tiles: HashSet<(i64, i64)> = robots
+                .iter()
+                .map(|r| {
+                    (
+                        (r[0] + r[2] * t).rem_euclid(101),
+                        (r[1] + r[3] * t).rem_euclid(103),
+                    )
+                })
+                .collect();
+            tiles.len() == robots.len()
+        };
+
+        let first = (1..101 * 103).find(|&t| distinct_at(t));
+        assert!(first.is_some());
+    }
+
+    #[test]
+    fn programs_have_a_quine() {
+        let input = day_17(&mut Rng::new(4), 0);
+        let program: Vec<u8> = input
+            .lines()
+            .last()
+            .unwrap()
+            .trim_start_matches("Program: ")
+            .split(',')
+            .map(|x| x.parse().unwrap())
+            .collect();
+        let a = find_quine(&program).unwrap();
+        assert_eq!(run_program(&program, a), program);
+    }
+}
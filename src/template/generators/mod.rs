@@ -0,0 +1,110 @@
+use crate::template::Day;
+
+pub use rng::Rng;
+
+mod days;
+mod rng;
+
+type Generate = fn(&mut Rng, usize) -> String;
+
+/// Generates synthetic puzzle inputs of a given size for one day.
+///
+/// Generated inputs follow the format and guarantees of the real inputs, so they can be used to
+/// stress test solutions or to measure how they scale.
+#[derive(Clone, Copy, Debug)]
+pub struct Generator {
+    pub day: Day,
+    /// The size closest to the real puzzle input, e.g. the number of lines or the width of a map.
+    pub default_size: usize,
+    generate: Generate,
+}
+
+impl Generator {
+    /// Generates an input of the given size. The same seed always results in the same input.
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+const GENERATORS: &[(u8, usize, Generate)] = &[
+    (1, 1000, days::day_01),
+    (2, 1000, days::day_02),
+    (3, 600, days::day_03),
+    (4, 140, days::day_04),
+    (5, 200, days::day_05),
+    (6, 130, days::day_06),
+    (7, 850, days::day_07),
+    (8, 50, days::day_08),
+    (9, 19999, days::day_09),
+    (10, 50, days::day_10),
+    (11, 8, days::day_11),
+    (13, 320, days::day_13),
+    (14, 500, days::day_14),
+    (17, 1, days::day_17),
+];
+
+/// Returns the input generator for a day, if there is one.
+pub fn generator(day: Day) -> Option<Generator> {
+    all_generators().find(|generator| generator.day == day)
+}
+
+/// Returns the input generators of all days that have one.
+pub fn all_generators() -> impl Iterator<Item = Generator> {
+    GENERATORS
+        .iter()
+        .map(|&(day, default_size, generate)| Generator {
+            day: Day::__new_unchecked(day),
+            default_size,
+            generate,
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inputs_are_reproducible() {
+        for generator in all_generators() {
+            let size = generator.default_size.min(20);
+            assert_eq!(
+                generator.generate(size, 7),
+                generator.generate(size, 7),
+                "day {}",
+                generator.day
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_change_inputs() {
+        for generator in all_generators() {
+            let size = generator.default_size.min(20);
+            assert_ne!(
+                generator.generate(size, 1),
+                generator.generate(size, 2),
+                "day {}",
+                generator.day
+            );
+        }
+    }
+
+    #[test]
+    fn inputs_end_with_a_newline() {
+        for generator in all_generators() {
+            assert!(
+                generator.generate(5, 0).ends_with('\n'),
+                "day {}",
+                generator.day
+            );
+        }
+    }
+
+    #[test]
+    fn looks_up_generators() {
+        assert!(generator(Day::new(6).unwrap()).is_some());
+        assert!(generator(Day::new(12).unwrap()).is_none());
+    }
+}
@@ -0,0 +1,95 @@
+/// A small, seedable pseudo random number generator, so generated inputs are reproducible across platforms.
+///
+/// Uses [SplitMix64](https://prng.di.unimi.it/splitmix64.c), which is fast and good enough for puzzle inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in the inclusive range `low..=high`.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns a number in the inclusive range `low..=high`.
+    pub fn range_i64(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = high.abs_diff(low);
+        low.wrapping_add_unsigned(self.range(0, span))
+    }
+
+    /// Returns an index in `0..len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "can not pick an index of an empty collection");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // use the upper 53 bits to get a uniformly distributed float in `0..1`.
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles a slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((3..=9).contains(&rng.range(3, 9)));
+            assert!((-5..=5).contains(&rng.range_i64(-5, 5)));
+            assert!(rng.index(4) < 4);
+        }
+        assert_eq!(rng.range(5, 5), 5);
+    }
+
+    #[test]
+    fn shuffles_all_items() {
+        let mut items: Vec<u32> = (0..50).collect();
+        Rng::new(3).shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,36 @@
+/// Tests that compare solutions against the answers recorded for the real puzzle input.
+use crate::template::answers::Answers;
+use crate::template::cases::assert_answer;
+use crate::template::runner::PartResult;
+use crate::template::{read_file, Day, InputError};
+
+/// Checks a part against its recorded answer for the real puzzle input.
+///
+/// `golden_tests!` ignores parts whose input or answer was missing at build time. If they are run
+/// anyway, e.g. with `cargo test -- --ignored`, the check is skipped with a message.
+#[track_caller]
+pub fn check_recorded_answer<R: PartResult>(day: Day, part: u8, func: impl Fn(&str) -> R) {
+    let input = match read_file("inputs", day) {
+        Ok(input) => input,
+        Err(InputError::Missing(_) | InputError::Empty(_)) => {
+            eprintln!("Skipping part {part} of day {day}: no puzzle input.");
+            return;
+        }
+        Err(e) => panic!("could not read puzzle input: {e}"),
+    };
+
+    let answers = Answers::read_from_file();
+    let Some(expected) = answers.get(day, part) else {
+        eprintln!(
+            "Skipping part {part} of day {day}: no recorded answer. Run `cargo time {} --store` to record it.",
+            day.into_inner()
+        );
+        return;
+    };
+
+    assert_answer(
+        func(&input),
+        expected,
+        &format!("part {part} of day {day} (recorded answer)"),
+    );
+}
@@ -0,0 +1,290 @@
+/// Selects and reads the input a solution binary runs against.
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+use crate::template::commands::exit_codes;
+use crate::template::{read_file, read_file_part, Day};
+
+/// An error which can be returned when reading an input file.
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Empty(PathBuf),
+    InvalidUtf8(PathBuf),
+    CrlfLineEndings(PathBuf),
+    IO(PathBuf, io::Error),
+}
+
+impl InputError {
+    /// The path that was attempted to be read.
+    pub fn path(&self) -> &Path {
+        match self {
+            InputError::Missing(path)
+            | InputError::Empty(path)
+            | InputError::InvalidUtf8(path)
+            | InputError::CrlfLineEndings(path)
+            | InputError::IO(path, _) => path,
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path().display();
+        match self {
+            InputError::Missing(_) => write!(f, "could not find \"{path}\"."),
+            InputError::Empty(_) => write!(f, "\"{path}\" is empty."),
+            InputError::InvalidUtf8(_) => write!(f, "\"{path}\" is not valid UTF-8."),
+            InputError::CrlfLineEndings(_) => write!(
+                f,
+                "\"{path}\" has CRLF line endings, convert it to LF line endings."
+            ),
+            InputError::IO(_, e) => write!(f, "could not read \"{path}\": {e}"),
+        }
+    }
+}
+
+/// Reads a text file to a string, rejecting contents solutions can not be expected to handle.
+pub(crate) fn read_path(path: &Path) -> Result<String, InputError> {
+    let bytes = fs::read(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => InputError::Missing(path.into()),
+        _ => InputError::IO(path.into(), e),
+    })?;
+    validate(path, bytes)
+}
+
+fn validate(path: &Path, bytes: Vec<u8>) -> Result<String, InputError> {
+    let contents = String::from_utf8(bytes).map_err(|_| InputError::InvalidUtf8(path.into()))?;
+
+    if contents.trim().is_empty() {
+        Err(InputError::Empty(path.into()))
+    } else if contents.contains("\r\n") {
+        Err(InputError::CrlfLineEndings(path.into()))
+    } else {
+        Ok(contents)
+    }
+}
+
+/// Where a solution binary reads its puzzle input from.
+///
+/// Selected with the `--input <path>`, `--example` and `--example-part <n>` flags.
+/// Passing `-` as the input path reads from stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `<data_dir>/inputs`.
+    Puzzle,
+    /// The example in `<data_dir>/examples`.
+    Example,
+    /// An additional example in `<data_dir>/examples`, e.g. `01-2.txt`.
+    ExamplePart(u8),
+    /// An arbitrary file.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+impl InputSource {
+    /// Determines the input source from the arguments the current process was called with.
+    pub fn from_args() -> Result<Self, String> {
+        Self::parse(env::args().skip(1))
+    }
+
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut source = InputSource::Puzzle;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let next = match arg.as_str() {
+                "--example" => InputSource::Example,
+                "--example-part" => {
+                    let part = args.next().and_then(|x| x.parse().ok()).ok_or(
+                        "`--example-part` expects a part number, e.g. `--example-part 2`.",
+                    )?;
+                    InputSource::ExamplePart(part)
+                }
+                "--input" => match args.next() {
+                    Some(path) if path == "-" => InputSource::Stdin,
+                    Some(path) => InputSource::File(path.into()),
+                    None => return Err("`--input` expects a file path or `-` for stdin.".into()),
+                },
+                _ => continue,
+            };
+
+            if source != InputSource::Puzzle {
+                return Err(
+                    "`--input`, `--example` and `--example-part` are mutually exclusive.".into(),
+                );
+            }
+            source = next;
+        }
+
+        Ok(source)
+    }
+
+    /// Converts the input source back into flags that can be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example => vec!["--example".into()],
+            InputSource::ExamplePart(part) => vec!["--example-part".into(), part.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Whether this is the real puzzle input, i.e. answers may be submitted.
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Reads the input for a day from this source.
+    pub fn read(&self, day: Day) -> Result<String, InputError> {
+        match self {
+            InputSource::Puzzle => read_file("inputs", day),
+            InputSource::Example => read_file("examples", day),
+            InputSource::ExamplePart(part) => read_file_part("examples", day, *part),
+            InputSource::File(path) => read_path(path),
+            InputSource::Stdin => {
+                let path = Path::new("<stdin>");
+                let mut bytes = vec![];
+                io::stdin()
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| InputError::IO(path.into(), e))?;
+                validate(path, bytes)
+            }
+        }
+    }
+
+    /// Suggests how to fix a failed read from this source.
+    fn hint(&self, day: Day, error: &InputError) -> Option<String> {
+        match (self, error) {
+            (InputSource::Puzzle, InputError::Missing(_) | InputError::Empty(_)) => Some(format!(
+                "Try running `cargo download {}` to download it.",
+                day.into_inner()
+            )),
+            (
+                InputSource::Example | InputSource::ExamplePart(_),
+                InputError::Missing(_) | InputError::Empty(_),
+            ) => Some("Paste the example from the puzzle description into it.".into()),
+            (_, InputError::CrlfLineEndings(_)) => Some(
+                "Try running `dos2unix` on it or check your git `core.autocrlf` setting.".into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::Example => write!(f, "example"),
+            InputSource::ExamplePart(part) => write!(f, "example part {part}"),
+            InputSource::File(_) => write!(f, "input file"),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// Reads the input for a solution binary, honouring the input selection flags.
+/// Prints a descriptive error and exits if the input can not be read.
+pub fn read_input(day: Day) -> String {
+    let source = match InputSource::from_args() {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(exit_codes::USAGE);
+        }
+    };
+
+    match source.read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read {source}: {e}");
+            if let Some(hint) = source.hint(day, &e) {
+                eprintln!("{hint}");
+            }
+            process::exit(exit_codes::INPUT_UNREADABLE);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{validate, InputError, InputSource};
+    use std::path::Path;
+
+    fn parse(args: &[&str]) -> Result<InputSource, String> {
+        InputSource::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn defaults_to_puzzle_input() {
+        assert_eq!(parse(&[]), Ok(InputSource::Puzzle));
+        assert_eq!(parse(&["--time"]), Ok(InputSource::Puzzle));
+    }
+
+    #[test]
+    fn parses_examples() {
+        assert_eq!(parse(&["--example"]), Ok(InputSource::Example));
+        assert_eq!(
+            parse(&["--time", "--example-part", "2"]),
+            Ok(InputSource::ExamplePart(2))
+        );
+    }
+
+    #[test]
+    fn parses_input_paths() {
+        assert_eq!(
+            parse(&["--input", "foo.txt"]),
+            Ok(InputSource::File("foo.txt".into()))
+        );
+        assert_eq!(parse(&["--input", "-"]), Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn round_trips_through_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example,
+            InputSource::ExamplePart(3),
+            InputSource::File("foo.txt".into()),
+            InputSource::Stdin,
+        ] {
+            assert_eq!(InputSource::parse(source.to_args()), Ok(source));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--example-part", "x"]).is_err());
+        assert!(parse(&["--example", "--input", "foo.txt"]).is_err());
+    }
+
+    #[test]
+    fn validates_contents() {
+        let path = Path::new("01.txt");
+        assert_eq!(
+            validate(path, b"1 2\n3 4\n".to_vec()).unwrap(),
+            "1 2\n3 4\n"
+        );
+        assert!(matches!(
+            validate(path, b" \n".to_vec()),
+            Err(InputError::Empty(_))
+        ));
+        assert!(matches!(
+            validate(path, b"1 2\r\n3 4\r\n".to_vec()),
+            Err(InputError::CrlfLineEndings(_))
+        ));
+        assert!(matches!(
+            validate(path, vec![0xff, 0xfe]),
+            Err(InputError::InvalidUtf8(_))
+        ));
+    }
+}
@@ -1,57 +1,82 @@
-use std::{env, fs};
+use std::env;
 
+use config::Config;
+
+pub mod answers;
 pub mod aoc_cli;
+pub mod cases;
 pub mod commands;
+pub mod config;
+pub mod generators;
+pub mod golden;
 pub mod runner;
+pub mod visualize;
 
 pub use day::*;
+pub use input::*;
 
 mod day;
+mod input;
 mod readme_benchmarks;
 mod run_multi;
+mod scaling;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, day: Day) -> Result<String, InputError> {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = cwd.join(Config::get().day_path(folder, day, "txt"));
+    read_path(&filepath)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(&Config::get().data_dir)
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_path(&filepath)
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternative implementations of a part can be listed after the day. They are only run with
+/// `cargo solve <day> --compare`, which checks that all implementations of a part return the same
+/// answer and benches them side by side.
+///
+/// ```ignore
+/// advent_of_code::solution!(6, alternatives {
+///     2 => [part_two_sequential],
+/// });
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1];);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2];);
+    };
+    ($day:expr, alternatives { $( $alt_part:literal => [ $( $alt:ident ),+ $(,)? ] ),+ $(,)? }) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]; $( [$alt_part, $( $alt )+] )+);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*; $( [$alt_part:literal, $( $alt:ident )+] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +86,81 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_input(DAY);
+            let mut succeeded = true;
+            if is_comparing() {
+                let implementations = [
+                    $( Implementation::new($part, stringify!($func), $func), )*
+                    $( $( Implementation::new($alt_part, stringify!($alt), $alt), )+ )*
+                ];
+                succeeded = compare_parts(&implementations, &input);
+            } else {
+                $( succeeded &= run_part($func, &input, DAY, $part); )*
+            }
+            if !succeeded {
+                std::process::exit($crate::template::commands::exit_codes::SOLUTION_FAILED);
+            }
+        }
+    };
+}
+
+/// Generates one test per case in the cases file of the current day (`data/examples/NN.cases`).
+///
+/// Takes the parts to check. The tests are generated from the cases file when the solution is
+/// built, see `build.rs`, so a test is added for every new case.
+///
+/// ```ignore
+/// advent_of_code::example_cases! {
+///     1 => part_one, 2 => part_two,
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_cases {
+    ($( $part:literal => $func:path ),+ $(,)?) => {
+        fn check_case(name: &str) {
+            let case = $crate::template::cases::load_case(DAY, name);
+            $( $crate::template::cases::check_part(&case, $part, $func); )+
+        }
+
+        include!(concat!(env!("OUT_DIR"), "/cases/", env!("CARGO_BIN_NAME"), ".rs"));
+    };
+}
+
+/// Generates a test per part that compares the solution against the answer recorded for the real puzzle input.
+///
+/// Answers are recorded by `cargo time --store`. Parts whose puzzle input or recorded answer was
+/// missing when the solution was built, e.g. in CI, are marked with `#[ignore]`, see `build.rs`.
+/// Further attributes can be added per part.
+///
+/// ```ignore
+/// advent_of_code::golden_tests! {
+///     1 => part_one,
+///     #[ignore = "slow in debug builds"]
+///     2 => part_two,
+/// }
+/// ```
+#[macro_export]
+macro_rules! golden_tests {
+    // parts are matched as token trees, so `golden_ignore!` can compare them to its part numbers.
+    (@tests [$($meta:tt)*] #[$attribute:meta] $($rest:tt)*) => {
+        $crate::golden_tests!(@tests [$($meta)* #[$attribute]] $($rest)*);
+    };
+    (@tests [$($meta:tt)*] $part:tt => $func:ident $(, $($rest:tt)*)?) => {
+        golden_ignore! { $part,
+            #[test]
+            $($meta)*
+            fn $func() {
+                $crate::template::golden::check_recorded_answer(super::DAY, $part, super::$func);
+            }
+        }
+        $( $crate::golden_tests!(@tests [] $($rest)*); )?
+    };
+    (@tests []) => {};
+    ($($tests:tt)+) => {
+        mod golden {
+            include!(concat!(env!("OUT_DIR"), "/golden/", env!("CARGO_BIN_NAME"), ".rs"));
+
+            $crate::golden_tests!(@tests [] $($tests)+);
         }
     };
 }
@@ -1,14 +1,47 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{commands::CommandError, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    answers::{Answers, DayAnswers},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// The state a day ended up in after running its solution.
+enum DayState {
+    Solved,
+    NotSolved,
+    Failed,
+    InputUnreadable,
+    Crashed(String),
+}
+
+impl Display for DayState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayState::Solved => write!(f, "solved"),
+            DayState::NotSolved => write!(f, "not solved"),
+            DayState::Failed => write!(f, "failed"),
+            DayState::InputUnreadable => write!(f, "input unreadable"),
+            DayState::Crashed(reason) => write!(f, "{ANSI_BOLD}crashed{ANSI_RESET} ({reason})"),
+        }
+    }
+}
+
+/// Runs the solutions for a set of days one after the other.
+///
+/// Returns the timings of the run if `is_timed` is set and the answers of all solved parts, alongside the first failure that occurred.
+/// A failing day does not stop the remaining days from running. Days that crash are excluded from the timings.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+) -> (Option<Timings>, Answers, Result<(), CommandError>) {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers: Vec<DayAnswers> = Vec::with_capacity(days_to_run.len());
+    let mut states: Vec<(Day, DayState)> = Vec::with_capacity(days_to_run.len());
+    let mut result = Ok(());
 
     let mut need_space = false;
 
@@ -24,25 +57,80 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let run = match child_commands::run_solution(day, is_timed, is_release) {
+                Ok(Some(run)) => run,
+                Ok(None) => {
+                    println!("Not solved.");
+                    states.push((day, DayState::NotSolved));
+                    return;
+                }
+                Err(e) => {
+                    let reason = format!("could not run solution: {e}");
+                    println!("Crashed: {reason}");
+                    if result.is_ok() {
+                        result = Err(CommandError::Crashed(day, reason.clone()));
+                    }
+                    states.push((day, DayState::Crashed(reason)));
+                    return;
+                }
+            };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
+            if let Some(reason) = run.crash_reason() {
+                println!("Crashed: {reason}");
+                // crashes take precedence over other failures when determining the exit code.
+                if !matches!(result, Err(CommandError::Crashed(_, _))) {
+                    result = Err(CommandError::Crashed(day, reason.clone()));
+                }
+                states.push((day, DayState::Crashed(reason)));
+                return;
+            }
+
+            let status = CommandError::from_solution_status(day, run.status);
+            let state = match status {
+                Err(CommandError::SolutionFailed(_)) => DayState::Failed,
+                Err(CommandError::InputUnreadable(_)) => DayState::InputUnreadable,
+                _ if run.output.is_empty() => DayState::NotSolved,
+                _ => DayState::Solved,
+            };
+            if result.is_ok() {
+                result = status;
+            }
+
+            if !run.output.is_empty() {
+                let val = child_commands::parse_exec_time(&run.output, day);
                 timings.push(val);
+
+                let parsed = child_commands::parse_answers(&run.output);
+                let day_answers = DayAnswers::from_parsed(day, &parsed);
+                if !day_answers.is_empty() {
+                    answers.push(day_answers);
+                }
+            } else if matches!(state, DayState::NotSolved) {
+                println!("Not solved.");
             }
+            states.push((day, state));
         });
 
+    if states
+        .iter()
+        .any(|(_, state)| !matches!(state, DayState::Solved | DayState::NotSolved))
+    {
+        println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+        println!("-------");
+        for (day, state) in &states {
+            println!("Day {day}: {state}");
+        }
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        (Some(timings), Answers { data: answers }, result)
     } else {
-        None
+        (None, Answers { data: answers }, result)
     }
 }
 
@@ -53,6 +141,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture output."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -68,36 +165,95 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        commands::exit_codes, runner::FAILED_MARKER, Day, InputSource, ANSI_BOLD, ANSI_RESET,
+    };
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Command, ExitStatus, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// The result of running a solution bin.
+    pub struct SolutionRun {
+        /// Lines the solution printed to stdout.
+        pub output: Vec<String>,
+        pub status: ExitStatus,
+        /// The panic message, if the solution panicked.
+        pub panic: Option<String>,
+        /// Lines the solution printed to stderr.
+        pub errors: Vec<String>,
+    }
+
+    impl SolutionRun {
+        /// Describes why the solution crashed, if it did not exit normally or report a failure.
+        pub fn crash_reason(&self) -> Option<String> {
+            if let Some(panic) = &self.panic {
+                return Some(panic.clone());
+            }
+
+            match self.status.code() {
+                Some(0 | exit_codes::SOLUTION_FAILED | exit_codes::INPUT_UNREADABLE) => None,
+                _ => Some(format!("exited with {}", self.status)),
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day. Returns `None` if the day has not been scaffolded yet.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Option<SolutionRun>, Error> {
+        spawn_solution(day, is_timed, is_release, &InputSource::Puzzle, true)
+    }
+
+    /// Run the solution bin for a given day against an input without forwarding its output.
+    /// Returns `None` if the day has not been scaffolded yet.
+    pub fn capture_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: &InputSource,
+    ) -> Result<Option<SolutionRun>, Error> {
+        spawn_solution(day, is_timed, is_release, input, false)
+    }
+
+    fn spawn_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        input: &InputSource,
+        forward_output: bool,
+    ) -> Result<Option<SolutionRun>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
+        args.push("--".into());
+        args.extend(input.to_args());
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // optionally forward output to stdout/stderr while grabbing the lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -111,21 +267,50 @@ pub mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            stderr
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    if forward_output {
+                        eprintln!("{line}");
+                    }
+                    line
+                })
+                .collect::<Vec<_>>()
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if forward_output {
+                println!("{line}");
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let errors = thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Some(SolutionRun {
+            output,
+            status,
+            panic: parse_panic(&errors),
+            errors,
+        }))
+    }
+
+    /// Extracts the panic message from the stderr output of a solution.
+    /// Handles both the `panicked at 'msg', location` and the `panicked at location:\nmsg` formats.
+    fn parse_panic(errors: &[String]) -> Option<String> {
+        let index = errors.iter().position(|l| l.contains(" panicked at "))?;
+        let (_, panic) = errors[index].split_once(" panicked at ")?;
+
+        match panic.strip_suffix(':') {
+            Some(location) => match errors.get(index + 1) {
+                Some(message) => Some(format!("{message} at {location}")),
+                None => Some(format!("panicked at {location}")),
+            },
+            None => Some(panic.to_string()),
+        }
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -134,8 +319,16 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            failed_parts: vec![],
         };
 
+        output
+            .iter()
+            .filter_map(|l| l.strip_prefix("Part "))
+            .filter(|l| l.contains(FAILED_MARKER))
+            .filter_map(|l| l.split(':').next()?.parse().ok())
+            .for_each(|part| timings.failed_parts.push(part));
+
         output
             .iter()
             .filter_map(|l| {
@@ -164,6 +357,66 @@ pub mod child_commands {
         timings
     }
 
+    /// Extracts the benchmarked time of each part in nanoseconds from the output of a timed solution.
+    pub fn parse_part_nanos(output: &[String]) -> BTreeMap<u8, f64> {
+        output
+            .iter()
+            .filter(|l| l.contains(" samples)"))
+            .filter_map(|l| {
+                let part = l.split(':').next()?.strip_prefix("Part ")?.parse().ok()?;
+                let (_, nanos) = parse_time(l)?;
+                Some((part, nanos))
+            })
+            .collect()
+    }
+
+    /// Extracts the answer printed for each part from the output of a solution.
+    pub fn parse_answers(output: &[String]) -> BTreeMap<u8, String> {
+        let mut answers: BTreeMap<u8, String> = BTreeMap::new();
+        let mut multiline_part = None;
+
+        for line in output {
+            // intermediate results are overwritten by moving to the start of the line.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+
+            let parsed = line
+                .strip_prefix("Part ")
+                .and_then(|l| l.split_once(": "))
+                .and_then(|(part, rest)| Some((part.parse::<u8>().ok()?, rest)));
+
+            match parsed {
+                Some((part, rest)) => {
+                    multiline_part = None;
+                    let answer = if rest.starts_with('▼') {
+                        multiline_part = Some(part);
+                        String::new()
+                    } else if let Some(answer) = rest
+                        .strip_prefix(ANSI_BOLD)
+                        .and_then(|r| Some(&r[..r.rfind(ANSI_RESET)?]))
+                    {
+                        answer.to_string()
+                    } else {
+                        rest.trim_end().to_string()
+                    };
+                    answers.insert(part, answer);
+                }
+                None => {
+                    if let Some(answer) = multiline_part.and_then(|part| answers.get_mut(&part)) {
+                        if !answer.is_empty() {
+                            answer.push('\n');
+                        }
+                        answer.push_str(line);
+                    }
+                }
+            }
+        }
+
+        answers
+            .into_iter()
+            .map(|(part, answer)| (part, answer.trim_end().to_string()))
+            .collect()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +427,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +458,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time, parse_panic, parse_part_nanos};
 
         use crate::day;
 
@@ -224,6 +477,17 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
+        #[test]
+        fn parses_part_times() {
+            let res = parse_part_nanos(&[
+                "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                "Part 2: ✖        ".into(),
+                "".into(),
+            ]);
+            assert_eq!(res.len(), 1);
+            assert_approx_eq!(res[&1], 74.13_f64);
+        }
+
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
@@ -252,6 +516,71 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.failed_parts.is_empty(), true);
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖ Error: invalid instruction `9,1`".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.failed_parts, vec![2]);
+        }
+
+        #[test]
+        fn parses_panic_messages() {
+            let res = parse_panic(&[
+                "thread 'main' panicked at src/bin/05.rs:8:40:".into(),
+                "called `Option::unwrap()` on a `None` value".into(),
+                "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
+                    .into(),
+            ]);
+            assert_eq!(
+                res.unwrap(),
+                "called `Option::unwrap()` on a `None` value at src/bin/05.rs:8:40"
+            );
+        }
+
+        #[test]
+        fn parses_legacy_panic_messages() {
+            let res = parse_panic(&["thread 'main' panicked at 'Loop', src/bin/06.rs:10:5".into()]);
+            assert_eq!(res.unwrap(), "'Loop', src/bin/06.rs:10:5");
+        }
+
+        #[test]
+        fn ignores_output_without_panics() {
+            let res = parse_panic(&["Failed to read puzzle input".into()]);
+            assert_eq!(res.is_none(), true);
+        }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: ✖\rPart 1: \x1b[1m11\x1b[0m (17.2µs @ 10 samples)".into(),
+                "Part 2: ✖\rPart 2: ✖ Error: invalid instruction `9,4`".into(),
+            ]);
+            assert_eq!(res[&1], "11");
+            assert_eq!(res[&2], "✖ Error: invalid instruction `9,4`");
+        }
+
+        #[test]
+        fn parses_multiline_answers() {
+            let res = parse_answers(&[
+                "Part 1: ▼ (1.0ms)".into(),
+                "#..".into(),
+                ".#.".into(),
+                "Part 2: \x1b[1m(1, 2)\x1b[0m (1.0µs)".into(),
+                "".into(),
+            ]);
+            assert_eq!(res[&1], "#..\n.#.");
+            assert_eq!(res[&2], "(1, 2)");
         }
     }
 }
@@ -3,33 +3,234 @@ use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::commands::exit_codes;
+use crate::template::config::Config;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, visualize, Day, InputSource, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The outcome of running a solution part.
+pub enum Answer<T> {
+    Solved(T),
+    Unsolved,
+    Failed(String),
+}
+
+/// Return types a solution part can have: `Option<T>`, or `Result<T, E>` for fallible solutions.
+pub trait PartResult {
+    type Output: Display;
+
+    fn into_answer(self) -> Answer<Self::Output>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Output = T;
+
+    fn into_answer(self) -> Answer<T> {
+        self.map_or(Answer::Unsolved, Answer::Solved)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Output = T;
+
+    fn into_answer(self) -> Answer<T> {
+        self.map_or_else(|e| Answer::Failed(e.to_string()), Answer::Solved)
+    }
+}
+
+/// Marks a part that returned an error in the runner output.
+pub(crate) const FAILED_MARKER: &str = "✖ Error:";
+
+/// Runs a solution part, prints its result and submits it if requested.
+/// Returns `false` if the part returned an error.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+) -> bool {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // only the first run of a part is visualized and reported on, not the runs while benching.
+    visualize::begin(day, part);
+    FIRST_RUN.store(true, Ordering::Relaxed);
+    let (result, duration, samples) = run_timed(
+        |input| func(input).into_answer(),
+        input,
+        |result| {
+            FIRST_RUN.store(false, Ordering::Relaxed);
+            visualize::end();
+            print_result(result, &part_str, "");
+        },
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match result {
+        Answer::Solved(result) => {
+            if let Some(Err(e)) = submit_result(result, day, part) {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(exit_codes::AOC_CLI_FAILED);
+            }
+            true
+        }
+        Answer::Unsolved => true,
+        Answer::Failed(_) => false,
+    }
+}
+
+type ErasedPart = Box<dyn Fn(&str) -> Answer<String>>;
+
+/// A named implementation of a solution part, as listed in the `solution!` macro.
+/// Its result is converted to a string so implementations with different return types can be compared.
+pub struct Implementation {
+    pub part: u8,
+    pub name: &'static str,
+    func: ErasedPart,
+}
+
+impl Implementation {
+    pub fn new<R: PartResult>(
+        part: u8,
+        name: &'static str,
+        func: impl Fn(&str) -> R + 'static,
+    ) -> Self {
+        Implementation {
+            part,
+            name,
+            func: Box::new(move |input| match func(input).into_answer() {
+                Answer::Solved(result) => Answer::Solved(result.to_string()),
+                Answer::Unsolved => Answer::Unsolved,
+                Answer::Failed(e) => Answer::Failed(e),
+            }),
+        }
+    }
+}
+
+/// Set while a part runs for the first time, before it is benched.
+static FIRST_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the solution was called with `--verbose` and a part is running for the first time.
+/// Solutions and helpers can print diagnostics to stderr when this is set.
+pub fn is_verbose() -> bool {
+    FIRST_RUN.load(Ordering::Relaxed) && env::args().any(|x| x == "--verbose")
+}
+
+/// Whether the solution was called with `--compare`.
+pub fn is_comparing() -> bool {
+    env::args().any(|x| x == "--compare")
+}
+
+/// Runs all implementations of each part, verifies that they return the same answer and benches them side by side.
+/// Returns `false` if an implementation failed or the implementations of a part disagree.
+pub fn compare_parts(implementations: &[Implementation], input: &str) -> bool {
+    let mut parts: Vec<u8> = implementations.iter().map(|i| i.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    let mut succeeded = true;
+    for part in parts {
+        let implementations: Vec<&Implementation> =
+            implementations.iter().filter(|i| i.part == part).collect();
+        succeeded &= compare_part(part, &implementations, input);
+    }
+    succeeded
+}
+
+fn compare_part(part: u8, implementations: &[&Implementation], input: &str) -> bool {
+    let width = implementations
+        .iter()
+        .map(|i| i.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Implementation".len());
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    println!(
+        "{:<width$}  {:<16}  {:<28}  Relative",
+        "Implementation", "Answer", "Time"
+    );
+
+    let runs: Vec<(Answer<String>, Duration)> = implementations
+        .iter()
+        .map(|implementation| {
+            let timer = Instant::now();
+            let answer = (implementation.func)(input);
+            (answer, timer.elapsed())
+        })
+        .collect();
+
+    // implementations that all leave the part unsolved agree, but there is nothing to bench.
+    if runs
+        .iter()
+        .all(|(answer, _)| matches!(answer, Answer::Unsolved))
+    {
+        for implementation in implementations {
+            println!("{:<width$}  ✖", implementation.name);
+        }
+        println!();
+        return true;
+    }
+
+    let expected = match &runs[0].0 {
+        Answer::Solved(result) => Some(result),
+        _ => None,
+    };
+    let agree = runs
+        .iter()
+        .all(|(answer, _)| matches!(answer, Answer::Solved(result) if Some(result) == expected));
+
+    if !agree {
+        for (implementation, (answer, _)) in implementations.iter().zip(&runs) {
+            let answer = match answer {
+                Answer::Solved(result) => result.clone(),
+                Answer::Unsolved => "✖".into(),
+                Answer::Failed(e) => format!("{FAILED_MARKER} {e}"),
+            };
+            println!("{:<width$}  {answer}", implementation.name);
+        }
+        println!("{FAILED_MARKER} the implementations of part {part} disagree.\n");
+        return false;
+    }
+
+    let mut reference = None;
+    for (implementation, (answer, base_time)) in implementations.iter().zip(&runs) {
+        let Answer::Solved(result) = answer else {
+            unreachable!()
+        };
+        let result = result.lines().next().unwrap_or_default();
+
+        print!("{:<width$}  {result:<16}", implementation.name);
+        let (duration, samples) = bench(|input| (implementation.func)(input), input, base_time);
+        let time = format!("{duration:.1?} @ {samples} samples");
+
+        let reference = *reference.get_or_insert(duration);
+        let relative = duration.as_secs_f64() / reference.as_secs_f64().max(f64::EPSILON);
+        print!("\r");
+        println!(
+            "{:<width$}  {result:<16}  {time:<28}  {relative:.2}x",
+            implementation.name
+        );
     }
+    println!();
+
+    true
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. the configured bench budget, 1 second by default, or 10 samples, whatever take longer.)
+///
+/// Parts that fail are never benched.
 fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+    func: impl Fn(I) -> Answer<T>,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl Fn(&Answer<T>),
+) -> (Answer<T>, Duration, u128) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,7 +244,9 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let is_failed = matches!(result, Answer::Failed(_));
+
+    let run = if !is_failed && std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -58,8 +261,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (Config::get().bench_budget.as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -94,11 +298,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Answer<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Answer::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +322,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Answer::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,12 +330,21 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Answer::Failed(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: {FAILED_MARKER} {e}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
+///  3. the solution ran against the real puzzle input.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
@@ -145,25 +358,75 @@ fn submit_result<T: Display>(
 
     if args.len() < 3 {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        process::exit(exit_codes::USAGE);
     }
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
     let Ok(part_submit) = args[part_index].parse::<u8>() else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        process::exit(exit_codes::USAGE);
     };
 
     if part_submit != part {
         return None;
     }
 
+    if let Ok(source) = InputSource::from_args() {
+        if !source.is_puzzle() {
+            eprintln!("Refusing to submit a result computed from {source}.");
+            process::exit(exit_codes::USAGE);
+        }
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        process::exit(exit_codes::AOC_CLI_MISSING);
     }
 
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare_parts, Implementation};
+
+    #[test]
+    fn detects_disagreeing_implementations() {
+        let implementations = [
+            Implementation::new(1, "one", |_: &str| Some(1)),
+            Implementation::new(1, "two", |_: &str| Some(2)),
+        ];
+        assert!(!compare_parts(&implementations, ""));
+    }
+
+    #[test]
+    fn accepts_implementations_that_are_all_unsolved() {
+        let implementations = [
+            Implementation::new(1, "one", |_: &str| None::<u32>),
+            Implementation::new(1, "two", |_: &str| None::<u32>),
+        ];
+        assert!(compare_parts(&implementations, ""));
+    }
+
+    #[test]
+    fn detects_partly_unsolved_implementations() {
+        let implementations = [
+            Implementation::new(1, "one", |_: &str| None::<u32>),
+            Implementation::new(1, "two", |_: &str| Some(2)),
+        ];
+        assert!(!compare_parts(&implementations, ""));
+    }
+
+    #[test]
+    fn detects_failing_implementations() {
+        let implementations = [
+            Implementation::new(2, "ok", |_: &str| Ok::<u32, String>(1)),
+            Implementation::new(2, "failing", |_: &str| Err::<u32, String>("oops".into())),
+        ];
+        assert!(!compare_parts(&implementations, ""));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt::Write, time::Duration};
+
+/// The benchmarked times of both parts on a generated input of one size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    /// The size passed to the generator, which means different things on different days.
+    pub size: usize,
+    /// The length of the generated input in bytes.
+    pub bytes: usize,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+impl Sample {
+    pub fn part(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1,
+            _ => self.part_2,
+        }
+    }
+}
+
+/// Returns `steps` sizes that double up to `max_size`, skipping duplicates of small sizes.
+pub fn sizes(max_size: usize, steps: u32) -> Vec<usize> {
+    let mut sizes: Vec<usize> = (0..steps)
+        .rev()
+        .map(|step| max_size.checked_shr(step).unwrap_or(0).max(1))
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+/// Whether the generated inputs differ in length, i.e. the generator makes use of the size.
+pub fn inputs_grow(samples: &[Sample]) -> bool {
+    samples
+        .iter()
+        .any(|sample| sample.bytes != samples[0].bytes)
+}
+
+/// Fits `time = c * bytes^k` to the samples of a part and returns the exponent `k`. The length
+/// of the input is used instead of the generator size, so the exponent is comparable between
+/// days where the size is a number of lines and days where it is the side of a grid.
+///
+/// Uses a least squares fit of a line through the samples on a log-log scale.
+/// Returns `None` if there are less than two samples with distinct input lengths.
+pub fn fit_exponent(samples: &[Sample], part: u8) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter_map(|sample| {
+            let nanos = sample.part(part)?;
+            (nanos > 0.0).then(|| ((sample.bytes as f64).ln(), nanos.ln()))
+        })
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > f64::EPSILON).then(|| covariance / variance)
+}
+
+/// Describes an exponent as the closest common complexity class.
+pub fn describe_exponent(exponent: f64) -> &'static str {
+    match exponent {
+        e if e < 0.5 => "~O(1)",
+        e if e < 1.5 => "~O(n)",
+        e if e < 2.5 => "~O(n²)",
+        e if e < 3.5 => "~O(n³)",
+        _ => "worse than O(n³)",
+    }
+}
+
+pub fn format_nanos(nanos: Option<f64>) -> String {
+    match nanos {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(nanos) => format!("{:.1?}", Duration::from_nanos(nanos as u64)),
+        None => "-".into(),
+    }
+}
+
+/// Formats the samples as CSV with one row per size and the times in nanoseconds.
+pub fn to_csv(samples: &[Sample]) -> String {
+    let mut csv = String::from("size,bytes,part_1_ns,part_2_ns\n");
+    for sample in samples {
+        let format = |nanos: Option<f64>| nanos.map(|n| n.to_string()).unwrap_or_default();
+        writeln!(
+            csv,
+            "{},{},{},{}",
+            sample.size,
+            sample.bytes,
+            format(sample.part_1),
+            format(sample.part_2)
+        )
+        .unwrap();
+    }
+    csv
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<Sample> {
+        [100, 200, 400, 800]
+            .into_iter()
+            .map(|size| Sample {
+                size,
+                bytes: size,
+                part_1: Some(f(size as f64)),
+                part_2: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_linear_curves() {
+        let exponent = fit_exponent(&samples(|n| 3.0 * n), 1).unwrap();
+        assert!((exponent - 1.0).abs() < 1e-9, "{exponent}");
+    }
+
+    #[test]
+    fn fits_quadratic_curves() {
+        let exponent = fit_exponent(&samples(|n| 0.5 * n * n + 10.0), 1).unwrap();
+        assert!((exponent - 2.0).abs() < 0.05, "{exponent}");
+    }
+
+    #[test]
+    fn fits_against_input_length() {
+        // a grid with side `size` has `size²` cells, so a linear solution takes `size²` time.
+        let samples: Vec<Sample> = samples(|n| n * n)
+            .into_iter()
+            .map(|sample| Sample {
+                bytes: sample.size * sample.size,
+                ..sample
+            })
+            .collect();
+        let exponent = fit_exponent(&samples, 1).unwrap();
+        assert!((exponent - 1.0).abs() < 1e-9, "{exponent}");
+        assert!(inputs_grow(&samples));
+    }
+
+    #[test]
+    fn detects_inputs_that_do_not_grow() {
+        let samples: Vec<Sample> = samples(|n| n)
+            .into_iter()
+            .map(|sample| Sample {
+                bytes: 97,
+                ..sample
+            })
+            .collect();
+        assert!(!inputs_grow(&samples));
+        assert_eq!(fit_exponent(&samples, 1), None);
+    }
+
+    #[test]
+    fn needs_distinct_sizes() {
+        assert_eq!(fit_exponent(&samples(|n| n), 2), None);
+        assert_eq!(fit_exponent(&samples(|n| n)[..1], 1), None);
+    }
+
+    #[test]
+    fn doubles_sizes() {
+        assert_eq!(sizes(1000, 4), vec![125, 250, 500, 1000]);
+        assert_eq!(sizes(8, 5), vec![1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let samples = vec![Sample {
+            size: 10,
+            bytes: 110,
+            part_1: Some(1.5),
+            part_2: None,
+        }];
+        assert_eq!(
+            to_csv(&samples),
+            "size,bytes,part_1_ns,part_2_ns\n10,110,1.5,\n"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+/// Encoding of animated GIFs.
+use std::collections::HashMap;
+
+use super::image::Image;
+use super::Color;
+
+/// Encodes images as an endlessly looping GIF animation.
+///
+/// All frames share one colour table. If the images use more than 256 colours,
+/// colours are reduced to a 6x6x6 colour cube.
+pub(super) fn encode(images: &[Image], fps: u32) -> Vec<u8> {
+    let width = images.iter().map(|i| i.width).max().unwrap_or(0);
+    let height = images.iter().map(|i| i.height).max().unwrap_or(0);
+
+    let mut palette: Vec<Color> = vec![];
+    let mut indices: HashMap<Color, u8> = HashMap::new();
+    let exact = images.iter().flat_map(|image| &image.pixels).all(|&color| {
+        if indices.contains_key(&color) {
+            return true;
+        }
+        if palette.len() == 256 {
+            return false;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        indices.insert(color, palette.len() as u8);
+        palette.push(color);
+        true
+    });
+
+    let index_of: Box<dyn Fn(Color) -> u8> = if exact {
+        Box::new(|color| indices[&color])
+    } else {
+        palette = (0..216).map(cube_color).collect();
+        Box::new(cube_index)
+    };
+
+    // the colour table has a size of a power of two, at least 2.
+    let table_bits = palette.len().max(2).next_power_of_two().trailing_zeros();
+    palette.resize(1 << table_bits, Color::BLACK);
+
+    let mut bytes = b"GIF89a".to_vec();
+    bytes.extend(to_u16(width).to_le_bytes());
+    bytes.extend(to_u16(height).to_le_bytes());
+    #[allow(clippy::cast_possible_truncation)]
+    let packed = 0xf0 | (table_bits - 1) as u8;
+    bytes.extend([packed, 0, 0]);
+    for &Color(r, g, b) in &palette {
+        bytes.extend([r, g, b]);
+    }
+
+    // loop forever.
+    bytes.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    let delay = u16::try_from(100 / fps.max(1)).unwrap_or(u16::MAX).max(1);
+    for image in images {
+        // graphic control extension with the frame delay in hundredths of a second.
+        bytes.extend([0x21, 0xf9, 0x04, 0x00]);
+        bytes.extend(delay.to_le_bytes());
+        bytes.extend([0x00, 0x00]);
+
+        // image descriptor placing the image in the top left corner, without a local colour table.
+        bytes.push(0x2c);
+        bytes.extend([0, 0, 0, 0]);
+        bytes.extend(to_u16(image.width).to_le_bytes());
+        bytes.extend(to_u16(image.height).to_le_bytes());
+        bytes.push(0);
+
+        let min_code_size = table_bits.max(2);
+        #[allow(clippy::cast_possible_truncation)]
+        bytes.push(min_code_size as u8);
+        let pixels: Vec<u8> = image.pixels.iter().map(|&c| index_of(c)).collect();
+        for block in lzw(&pixels, min_code_size).chunks(255) {
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.push(block.len() as u8);
+            bytes.extend(block);
+        }
+        bytes.push(0);
+    }
+
+    bytes.push(0x3b);
+    bytes
+}
+
+fn to_u16(n: usize) -> u16 {
+    u16::try_from(n).expect("GIF dimensions fit into 16 bits")
+}
+
+fn cube_color(index: u8) -> Color {
+    let level = |i: u8| i * 51;
+    Color(level(index / 36), level(index / 6 % 6), level(index % 6))
+}
+
+fn cube_index(Color(r, g, b): Color) -> u8 {
+    let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+    #[allow(clippy::cast_possible_truncation)]
+    let index = (level(r) * 36 + level(g) * 6 + level(b)) as u8;
+    index
+}
+
+/// Packs codes of varying length into bytes, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            #[allow(clippy::cast_possible_truncation)]
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses colour indices with the variable length LZW variant used by GIF.
+fn lzw(indices: &[u8], min_code_size: u32) -> Vec<u8> {
+    const MAX_CODE: u16 = 4095;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0,
+    };
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut last_code = end;
+
+    writer.write(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+
+    let mut current = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = codes.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+
+        writer.write(current, code_size);
+        last_code += 1;
+        codes.insert((current, index), last_code);
+        if u32::from(last_code) >= 1 << code_size {
+            code_size += 1;
+        }
+        if last_code == MAX_CODE {
+            // the code table is full, start over.
+            writer.write(clear, code_size);
+            codes.clear();
+            code_size = min_code_size + 1;
+            last_code = end;
+        }
+        current = u16::from(index);
+    }
+
+    writer.write(current, code_size);
+    // the decoder adds one more code when reading the last code, unless it directly follows a clear code.
+    if last_code > end && u32::from(last_code) + 1 >= 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    // reset before ending the stream, so the decoder reads the end code with the initial code size.
+    writer.write(clear, code_size);
+    writer.write(end, min_code_size + 1);
+    writer.finish()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    /// A straightforward GIF LZW decoder to check the encoder against.
+    fn decode(bytes: &[u8], min_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+
+        let mut output = vec![];
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let (mut buffer, mut bits, mut position) = (0u32, 0u32, 0usize);
+
+        loop {
+            while bits < code_size {
+                buffer |= u32::from(bytes[position]) << bits;
+                position += 1;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("invalid code {code}"),
+            };
+            if let Some(previous) = previous {
+                table.push([previous, vec![entry[0]]].concat());
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            output.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn compresses_losslessly() {
+        let inputs: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![1],
+            vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 3, 0, 0, 0],
+            (0..20_000).map(|i| ((i * 7 + i / 13) % 4) as u8).collect(),
+        ];
+        for input in inputs {
+            assert_eq!(decode(&lzw(&input, 2), 2), input);
+        }
+
+        // hit every possible state of the code table at the end of the stream.
+        let pattern: Vec<u8> = (0..6000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 30) as u8)
+            .collect();
+        for len in (0..pattern.len()).step_by(7) {
+            assert_eq!(decode(&lzw(&pattern[..len], 2), 2), &pattern[..len]);
+        }
+
+        let noisy: Vec<u8> = (0..30_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        assert_eq!(decode(&lzw(&noisy, 8), 8), noisy);
+    }
+
+    #[test]
+    fn maps_colors_to_the_cube() {
+        assert_eq!(cube_color(cube_index(Color::WHITE)), Color::WHITE);
+        assert_eq!(
+            cube_color(cube_index(Color(250, 3, 100))),
+            Color(255, 0, 102)
+        );
+    }
+}
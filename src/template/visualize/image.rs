@@ -0,0 +1,177 @@
+/// Rendering of frames to pixels, and encoding of pixels to PPM and PNG.
+use super::{Color, Frame};
+
+/// Width and height in pixels of a cell in rendered images.
+const CELL_SIZE: usize = 4;
+
+/// A rendered frame.
+pub(super) struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Color>,
+}
+
+impl Image {
+    /// Renders each cell of a frame as a square of its colour.
+    pub fn render(frame: &Frame) -> Self {
+        let width = frame.width() * CELL_SIZE;
+        let height = frame.height() * CELL_SIZE;
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for row in frame.rows() {
+            for _ in 0..CELL_SIZE {
+                for cell in row {
+                    pixels.extend([cell.pixel(); CELL_SIZE]);
+                }
+            }
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Encodes the image as a binary PPM (`P6`).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for &Color(r, g, b) in &self.pixels {
+            bytes.extend([r, g, b]);
+        }
+        bytes
+    }
+
+    /// Encodes the image as an RGB PNG. The image data is stored without compression.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        let mut header = vec![];
+        header.extend(to_u32(self.width).to_be_bytes());
+        header.extend(to_u32(self.height).to_be_bytes());
+        // bit depth 8, colour type RGB, default compression, filter and interlace methods.
+        header.extend([8, 2, 0, 0, 0]);
+        write_chunk(&mut bytes, b"IHDR", &header);
+
+        // each scanline starts with its filter type, which is always `None`.
+        let mut scanlines = Vec::with_capacity(self.height * (1 + self.width * 3));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            scanlines.push(0);
+            for &Color(r, g, b) in row {
+                scanlines.extend([r, g, b]);
+            }
+        }
+        write_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+        write_chunk(&mut bytes, b"IEND", &[]);
+
+        bytes
+    }
+}
+
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).expect("image dimensions fit into 32 bits")
+}
+
+fn write_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend(to_u32(data.len()).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    // deflate without a preset dictionary, using the fastest compression level.
+    let mut bytes = vec![0x78, 0x01];
+
+    let blocks: Vec<&[u8]> = if data.is_empty() {
+        vec![&[]]
+    } else {
+        data.chunks(MAX_BLOCK).collect()
+    };
+    for (i, block) in blocks.iter().enumerate() {
+        let is_last = i + 1 == blocks.len();
+        #[allow(clippy::cast_possible_truncation)]
+        let len = block.len() as u16;
+        bytes.push(u8::from(is_last));
+        bytes.extend(len.to_le_bytes());
+        bytes.extend((!len).to_le_bytes());
+        bytes.extend(*block);
+    }
+
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn renders_cells_as_squares() {
+        let mut frame = Frame::new(2, 1);
+        frame.set_color(1, 0, Color::RED);
+        let image = Image::render(&frame);
+
+        assert_eq!((image.width, image.height), (2 * CELL_SIZE, CELL_SIZE));
+        assert_eq!(image.pixels[0], Color::BLACK);
+        assert_eq!(image.pixels[image.width - 1], Color::RED);
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let image = Image {
+            width: 1,
+            height: 1,
+            pixels: vec![Color(1, 2, 3)],
+        };
+        assert_eq!(image.to_ppm(), b"P6\n1 1\n255\n\x01\x02\x03");
+    }
+
+    #[test]
+    fn splits_large_images_into_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+        // header, two block headers and the checksum.
+        assert_eq!(stream.len(), 2 + 2 * 5 + data.len() + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + 65_535], 1);
+    }
+}
@@ -0,0 +1,424 @@
+/// Frames that solutions can emit to visualize how they work.
+///
+/// Frames are only built and written when the solution runs with `--visualize <format>`,
+/// and only during the first run of a part, never while benching.
+///
+/// ```ignore
+/// use advent_of_code::template::visualize::{self, Color, Frame};
+///
+/// visualize::frame(|| {
+///     let mut frame = Frame::from_text(&map);
+///     frame.set_color(x, y, Color::RED);
+///     frame
+/// });
+/// ```
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::{env, fs, io};
+
+use crate::template::config::Config;
+use crate::template::Day;
+
+mod gif;
+mod image;
+mod terminal;
+
+/// A colour in RGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const GREEN: Color = Color(80, 200, 80);
+    pub const BLUE: Color = Color(38, 139, 210);
+    pub const YELLOW: Color = Color(230, 200, 40);
+
+    /// Blends two colours, `t = 0.0` resulting in `self` and `t = 1.0` in `other`.
+    pub fn mix(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let channel =
+            |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Color(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    /// The colour a cell without an explicit colour is drawn with in images.
+    fn for_char(ch: char) -> Color {
+        match ch {
+            ' ' | '.' => Color::BLACK,
+            '#' => Color::GRAY,
+            _ => {
+                // derive a stable, reasonably bright colour from the character.
+                let hash = (ch as u32).wrapping_mul(2_654_435_761);
+                let [r, g, b, _] = hash.to_le_bytes();
+                Color(r | 0x40, g | 0x40, b | 0x40)
+            }
+        }
+    }
+}
+
+/// A single cell of a [`Frame`]: a character, optionally drawn on a coloured background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    const BLANK: Cell = Cell {
+        ch: ' ',
+        color: None,
+    };
+
+    /// The colour of the cell in images.
+    fn pixel(&self) -> Color {
+        self.color.unwrap_or_else(|| Color::for_char(self.ch))
+    }
+}
+
+/// A grid of cells, addressed by column `x` and row `y`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// Creates a blank frame of the given size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame {
+            width,
+            height,
+            cells: vec![Cell::BLANK; width * height],
+        }
+    }
+
+    /// Creates a frame from lines of text. Shorter lines are padded with blank cells.
+    pub fn from_text(text: &str) -> Self {
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut frame = Frame::new(width, text.lines().count());
+        for (y, line) in text.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                frame.set_char(x, y, ch);
+            }
+        }
+        frame
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Sets the character of a cell. Cells outside of the frame are ignored.
+    pub fn set_char(&mut self, x: usize, y: usize, ch: char) {
+        if let Some(cell) = self.get_mut(x, y) {
+            cell.ch = ch;
+        }
+    }
+
+    /// Sets the colour of a cell. Cells outside of the frame are ignored.
+    pub fn set_color(&mut self, x: usize, y: usize, color: Color) {
+        if let Some(cell) = self.get_mut(x, y) {
+            cell.color = Some(color);
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where frames are written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// An animation drawn with ANSI escape codes.
+    Terminal,
+    /// One PPM image per frame.
+    Ppm,
+    /// One PNG image per frame.
+    Png,
+    /// An animated GIF per part.
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Format::Terminal),
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            _ => Err(format!(
+                "unknown visualization format `{s}`, expected `terminal`, `ppm`, `png` or `gif`."
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Terminal => write!(f, "terminal"),
+            Format::Ppm => write!(f, "ppm"),
+            Format::Png => write!(f, "png"),
+            Format::Gif => write!(f, "gif"),
+        }
+    }
+}
+
+/// Selected with the `--visualize <format>` and `--fps <n>` flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Settings {
+    pub format: Format,
+    /// Frames per second of terminal animations and GIFs.
+    pub fps: u32,
+}
+
+impl Settings {
+    pub const DEFAULT_FPS: u32 = 10;
+
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut format = None;
+        let mut fps = Settings::DEFAULT_FPS;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--visualize" => {
+                    let value = args
+                        .next()
+                        .ok_or("`--visualize` expects a format, e.g. `--visualize gif`.")?;
+                    format = Some(value.parse()?);
+                }
+                "--fps" => {
+                    fps = args
+                        .next()
+                        .and_then(|x| x.parse().ok())
+                        .filter(|&fps| fps > 0)
+                        .ok_or("`--fps` expects a positive number, e.g. `--fps 30`.")?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(format.map(|format| Settings { format, fps }))
+    }
+
+    /// Converts the settings back into flags that can be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--visualize".into(),
+            self.format.to_string(),
+            "--fps".into(),
+            self.fps.to_string(),
+        ]
+    }
+}
+
+/// The visualization settings the current process was called with, if any.
+fn settings() -> Option<Settings> {
+    static SETTINGS: OnceLock<Option<Settings>> = OnceLock::new();
+    *SETTINGS.get_or_init(|| match Settings::parse(env::args().skip(1)) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    })
+}
+
+/// The frames of the part that is currently running.
+struct Recording {
+    day: Day,
+    part: u8,
+    settings: Settings,
+    frames: usize,
+    /// Frames are only kept in memory for formats that are written at the end.
+    kept: Vec<Frame>,
+    /// Number of lines the last frame took up in the terminal.
+    drawn_lines: usize,
+}
+
+impl Recording {
+    /// Directory visualizations of the current part are written to, e.g. `data/visualizations/06-2`.
+    fn directory(&self) -> PathBuf {
+        Config::get()
+            .data_dir
+            .join("visualizations")
+            .join(format!("{}-{}", self.day, self.part))
+    }
+
+    fn add(&mut self, frame: Frame) -> io::Result<()> {
+        self.frames += 1;
+        match self.settings.format {
+            Format::Terminal => {
+                self.drawn_lines = terminal::draw(&frame, self.drawn_lines, self.settings.fps)?;
+            }
+            Format::Ppm | Format::Png => {
+                let directory = self.directory();
+                if self.frames == 1 {
+                    // remove frames of a previous, possibly longer run.
+                    let _ = fs::remove_dir_all(&directory);
+                    fs::create_dir_all(&directory)?;
+                }
+                let image = image::Image::render(&frame);
+                let (extension, bytes) = match self.settings.format {
+                    Format::Ppm => ("ppm", image.to_ppm()),
+                    _ => ("png", image.to_png()),
+                };
+                fs::write(
+                    directory.join(format!("{:05}.{extension}", self.frames)),
+                    bytes,
+                )?;
+            }
+            Format::Gif => self.kept.push(frame),
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<()> {
+        if self.frames == 0 {
+            return Ok(());
+        }
+
+        let location = match self.settings.format {
+            Format::Terminal => return Ok(()),
+            Format::Ppm | Format::Png => self.directory(),
+            Format::Gif => {
+                let path = self.directory().with_extension("gif");
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let images: Vec<image::Image> =
+                    self.kept.iter().map(image::Image::render).collect();
+                fs::write(&path, gif::encode(&images, self.settings.fps))?;
+                path
+            }
+        };
+
+        println!(
+            "Wrote {} frame(s) of part {} to \"{}\".",
+            self.frames,
+            self.part,
+            location.display()
+        );
+        Ok(())
+    }
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// Whether frames are currently being recorded. Useful to skip work that is only needed for visualizing.
+pub fn is_recording() -> bool {
+    RECORDING.lock().is_ok_and(|recording| recording.is_some())
+}
+
+/// Emits a frame. The frame is only built if the solution runs with `--visualize`.
+pub fn frame(build: impl FnOnce() -> Frame) {
+    let Ok(mut recording) = RECORDING.lock() else {
+        return;
+    };
+    let Some(recording) = recording.as_mut() else {
+        return;
+    };
+
+    if let Err(e) = recording.add(build()) {
+        eprintln!("Failed to write visualization frame: {e}");
+    }
+}
+
+/// Starts recording the frames of a part, if visualizing is enabled.
+pub(crate) fn begin(day: Day, part: u8) {
+    let Some(settings) = settings() else {
+        return;
+    };
+    if let Ok(mut recording) = RECORDING.lock() {
+        *recording = Some(Recording {
+            day,
+            part,
+            settings,
+            frames: 0,
+            kept: vec![],
+            drawn_lines: 0,
+        });
+    }
+}
+
+/// Stops recording and writes out the recorded frames.
+pub(crate) fn end() {
+    let recording = RECORDING.lock().ok().and_then(|mut r| r.take());
+    if let Some(recording) = recording {
+        if let Err(e) = recording.finish() {
+            eprintln!("Failed to write visualization: {e}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_settings() {
+        assert_eq!(Settings::parse(args("--example")), Ok(None));
+        assert_eq!(
+            Settings::parse(args("--visualize gif --fps 25")),
+            Ok(Some(Settings {
+                format: Format::Gif,
+                fps: 25
+            }))
+        );
+        assert!(Settings::parse(args("--visualize mp4")).is_err());
+        assert!(Settings::parse(args("--visualize png --fps 0")).is_err());
+    }
+
+    #[test]
+    fn builds_frames_from_text() {
+        let mut frame = Frame::from_text("#.\n.##\n");
+        frame.set_color(1, 1, Color::RED);
+        frame.set_char(5, 5, 'x');
+
+        assert_eq!((frame.width(), frame.height()), (3, 2));
+        assert_eq!(frame.get(2, 0).unwrap().ch, ' ');
+        assert_eq!(frame.get(1, 1).unwrap().color, Some(Color::RED));
+        assert_eq!(frame.get(3, 0), None);
+    }
+
+    #[test]
+    fn mixes_colors() {
+        assert_eq!(Color::BLACK.mix(Color::WHITE, 0.5), Color(128, 128, 128));
+        assert_eq!(Color::BLACK.mix(Color::WHITE, 2.0), Color::WHITE);
+    }
+}
@@ -0,0 +1,43 @@
+use std::io::{self, stdout, Write};
+use std::thread;
+use std::time::Duration;
+
+use super::{Color, Frame};
+use crate::template::ANSI_RESET;
+
+/// Draws a frame over the previously drawn one and waits for the duration of a frame.
+/// Returns the number of lines drawn.
+pub(super) fn draw(frame: &Frame, previous_lines: usize, fps: u32) -> io::Result<usize> {
+    let mut out = String::new();
+
+    if previous_lines > 0 {
+        // move the cursor back to the first line of the previous frame.
+        out.push_str(&format!("\x1b[{previous_lines}F"));
+    }
+
+    for row in frame.rows() {
+        let mut current: Option<Color> = None;
+        for cell in row {
+            if cell.color != current {
+                match cell.color {
+                    Some(Color(r, g, b)) => out.push_str(&format!("\x1b[48;2;{r};{g};{b}m")),
+                    None => out.push_str(ANSI_RESET),
+                }
+                current = cell.color;
+            }
+            out.push(cell.ch);
+        }
+        if current.is_some() {
+            out.push_str(ANSI_RESET);
+        }
+        // clear the remainder of lines from a wider previous frame.
+        out.push_str("\x1b[K\n");
+    }
+
+    let mut stdout = stdout().lock();
+    stdout.write_all(out.as_bytes())?;
+    stdout.flush()?;
+
+    thread::sleep(Duration::from_secs(1) / fps);
+    Ok(frame.height())
+}
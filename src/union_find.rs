@@ -0,0 +1,141 @@
+/// A disjoint-set forest of the elements `0..len`, for merging elements into groups and asking
+/// whether two elements are in the same group.
+///
+/// Uses path compression and union by size, so all operations take nearly constant time.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    /// The number of elements in each set, only up to date for roots.
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` sets of a single element each.
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Adds a new element in a set of its own and returns it.
+    pub fn add(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.sizes.push(1);
+        self.sets += 1;
+        element
+    }
+
+    /// The representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // point every element on the way directly to the root.
+        let mut current = element;
+        while self.parents[current] != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (larger, smaller) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set containing `element`.
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// The elements of every set, each in ascending order. Sets are ordered by their smallest
+    /// element.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut groups: Vec<Vec<usize>> = vec![];
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = groups.len();
+                groups.push(vec![]);
+            }
+            groups[index_of_root[root]].push(element);
+        }
+        groups
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.set_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 1));
+        assert!(sets.union(2, 3));
+        assert!(!sets.union(0, 4));
+
+        assert_eq!(sets.set_count(), 3);
+        assert!(sets.connected(4, 0));
+        assert!(!sets.connected(4, 3));
+        assert_eq!(sets.size_of(1), 3);
+        assert_eq!(sets.groups(), vec![vec![0, 1, 4], vec![2, 3], vec![5]]);
+    }
+
+    #[test]
+    fn compresses_long_chains() {
+        let mut sets = UnionFind::new(0);
+        assert!(sets.is_empty());
+        let elements: Vec<usize> = (0..10_000).map(|_| sets.add()).collect();
+        for pair in elements.windows(2) {
+            sets.union(pair[1], pair[0]);
+        }
+        assert_eq!(sets.len(), 10_000);
+        assert_eq!(sets.set_count(), 1);
+        assert_eq!(sets.size_of(9_999), 10_000);
+        assert!(sets.connected(0, 9_999));
+    }
+}